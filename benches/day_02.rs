@@ -0,0 +1,42 @@
+use aoc_2022::day_02::{
+    game::CyclicGame,
+    lut, parse_hands, parse_outcomes, score_hands, score_outcomes,
+    scoring::ScoringRules,
+    tournament::{SeededRandom, Strategy},
+};
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+
+/// A guide of the size of a puzzle input, with uniformly random codes.
+fn guide(game: &CyclicGame) -> String {
+    let mut random = SeededRandom::new(2022);
+    (0..2500)
+        .map(|_| {
+            let enemy = random.play(game, &[]).index() as u8;
+            let code = random.play(game, &[]).index() as u8;
+            format!("{} {}\n", (b'A' + enemy) as char, (b'X' + code) as char)
+        })
+        .collect()
+}
+
+fn bench_day_02(c: &mut Criterion) {
+    let game = CyclicGame::rock_paper_scissors();
+    let input = guide(&game);
+    let rules = ScoringRules::standard(&game);
+
+    let mut group = c.benchmark_group("day 2");
+    group.bench_function("generic", |b| {
+        b.iter(|| {
+            let input = black_box(input.as_str());
+            let part1 = score_hands(&game, &rules, &parse_hands(&game, input));
+            let part2 = score_outcomes(&game, &rules, &parse_outcomes(&game, input));
+            (part1, part2)
+        })
+    });
+    group.bench_function("lookup table", |b| {
+        b.iter(|| lut::solve(black_box(input.as_bytes())))
+    });
+    group.finish();
+}
+
+criterion_group!(benches, bench_day_02);
+criterion_main!(benches);
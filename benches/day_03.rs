@@ -0,0 +1,107 @@
+use std::collections::HashSet;
+
+use aoc_2022::day_03::item_set::ItemSet;
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+
+/// Rucksacks of the size of a puzzle input, with pseudo-random items.
+fn rucksacks() -> Vec<String> {
+    let items = b"abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ";
+    let mut state = 1u64;
+    (0..300)
+        .map(|_| {
+            (0..32)
+                .map(|_| {
+                    state = state.wrapping_mul(6364136223846793005).wrapping_add(1);
+                    items[(state >> 33) as usize % items.len()] as char
+                })
+                .collect()
+        })
+        .collect()
+}
+
+/// Items in both halves, as found before `ItemSet` existed.
+fn hash_set_compartments(rucksack: &str) -> Vec<char> {
+    let (left, right) = rucksack.split_at(rucksack.len() / 2);
+    let left = left.chars().collect::<HashSet<_>>();
+    let duplicates = right
+        .chars()
+        .filter(|c| left.contains(c))
+        .collect::<HashSet<_>>();
+    duplicates.into_iter().collect()
+}
+
+/// Items in all three rucksacks, as found before `ItemSet` existed.
+fn hash_set_group(group: &[String]) -> Vec<char> {
+    let mut common = group[0].chars().collect::<HashSet<_>>();
+    for rucksack in &group[1..] {
+        common = rucksack.chars().filter(|c| common.contains(c)).collect();
+    }
+    common.into_iter().collect()
+}
+
+fn item_set(items: &str) -> ItemSet {
+    ItemSet::try_from(items).expect("the rucksacks only contain letters")
+}
+
+fn item_set_compartments(rucksack: &str) -> Vec<char> {
+    let (left, right) = rucksack.split_at(rucksack.len() / 2);
+    item_set(left)
+        .intersection(item_set(right))
+        .iter()
+        .collect()
+}
+
+fn item_set_group(group: &[String]) -> Vec<char> {
+    group
+        .iter()
+        .map(|rucksack| item_set(rucksack))
+        .reduce(ItemSet::intersection)
+        .unwrap_or_default()
+        .iter()
+        .collect()
+}
+
+fn bench_day_03(c: &mut Criterion) {
+    let rucksacks = rucksacks();
+
+    let mut group = c.benchmark_group("day 3 compartments");
+    group.bench_function("hash set", |b| {
+        b.iter(|| {
+            black_box(&rucksacks)
+                .iter()
+                .map(|rucksack| hash_set_compartments(rucksack))
+                .collect::<Vec<_>>()
+        })
+    });
+    group.bench_function("item set", |b| {
+        b.iter(|| {
+            black_box(&rucksacks)
+                .iter()
+                .map(|rucksack| item_set_compartments(rucksack))
+                .collect::<Vec<_>>()
+        })
+    });
+    group.finish();
+
+    let mut group = c.benchmark_group("day 3 groups");
+    group.bench_function("hash set", |b| {
+        b.iter(|| {
+            black_box(&rucksacks)
+                .chunks(3)
+                .map(hash_set_group)
+                .collect::<Vec<_>>()
+        })
+    });
+    group.bench_function("item set", |b| {
+        b.iter(|| {
+            black_box(&rucksacks)
+                .chunks(3)
+                .map(item_set_group)
+                .collect::<Vec<_>>()
+        })
+    });
+    group.finish();
+}
+
+criterion_group!(benches, bench_day_03);
+criterion_main!(benches);
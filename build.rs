@@ -0,0 +1,23 @@
+use std::{env, fs, path::Path};
+
+fn main() {
+    println!("cargo:rerun-if-changed=src/ffi.rs");
+    println!("cargo:rerun-if-changed=cbindgen.toml");
+
+    let crate_dir = env::var("CARGO_MANIFEST_DIR").unwrap();
+    let config = cbindgen::Config::from_root_or_default(&crate_dir);
+
+    let header = Path::new(&env::var("OUT_DIR").unwrap()).join("aoc_2022.h");
+    cbindgen::Builder::new()
+        .with_src(format!("{crate_dir}/src/ffi.rs"))
+        .with_config(config)
+        .generate()
+        .expect("Unable to generate C header")
+        .write_to_file(&header);
+
+    // the header in the source tree is only rewritten on request
+    if env::var_os("CARGO_FEATURE_C_HEADER").is_some() {
+        fs::copy(&header, format!("{crate_dir}/include/aoc_2022.h"))
+            .expect("Unable to update include/aoc_2022.h");
+    }
+}
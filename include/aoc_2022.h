@@ -0,0 +1,62 @@
+#ifndef AOC_2022_H
+#define AOC_2022_H
+
+/* Generated by cbindgen from src/ffi.rs, do not edit by hand. */
+
+#include <stdarg.h>
+#include <stdbool.h>
+#include <stddef.h>
+#include <stdint.h>
+#include <stdlib.h>
+
+/**
+ * Status returned by `aoc_solve`.
+ */
+typedef enum AocStatus {
+  AOC_STATUS_OK = 0,
+  AOC_STATUS_UNKNOWN_DAY = 1,
+  AOC_STATUS_UNKNOWN_PART = 2,
+  AOC_STATUS_INVALID_INPUT = 3,
+  AOC_STATUS_NULL_POINTER = 4,
+  AOC_STATUS_PANICKED = 5,
+} AocStatus;
+
+/**
+ * Get all days which can be solved.
+ *
+ * The returned array is static and must not be freed. Its length is written to `len`.
+ *
+ * # Safety
+ *
+ * `len` must be null or point to writable memory for a `size_t`.
+ */
+const uint8_t *aoc_available_days(size_t *len);
+
+/**
+ * Solve a part (`1` or `2`) of a day for the given input buffer.
+ *
+ * On success, the answer is written to `answer` and `AOC_STATUS_OK` is returned. On any other
+ * status, `answer` receives a message describing the error instead. In both cases the string is
+ * owned by the caller and has to be released with `aoc_string_free`.
+ *
+ * # Safety
+ *
+ * `input` must point to `input_len` readable bytes (or be null if `input_len` is `0`) and
+ * `answer` must be null or point to writable memory for a `char *`.
+ */
+enum AocStatus aoc_solve(uint8_t day,
+                         uint8_t part,
+                         const uint8_t *input,
+                         size_t input_len,
+                         char **answer);
+
+/**
+ * Free a string returned by this library. Passing null is a no-op.
+ *
+ * # Safety
+ *
+ * `string` must have been returned by this library and must not be used afterwards.
+ */
+void aoc_string_free(char *string);
+
+#endif /* AOC_2022_H */
@@ -0,0 +1,337 @@
+//! Performance baselines to detect regressions in the time the solutions take.
+
+use std::{fmt, time::Duration};
+
+use serde::{Deserialize, Serialize};
+
+use crate::solver::{self, Part, SolveError};
+
+/// Factor to turn the median absolute deviation into an estimate of the standard deviation of
+/// normally distributed samples.
+const MAD_SCALE: f64 = 1.4826;
+
+/// Number of (estimated) standard deviations a slowdown has to exceed to not be considered noise.
+const NOISE_THRESHOLD: f64 = 3.0;
+
+/// Robust summary of repeated timings of the same phase.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct Timing {
+    pub median_ns: f64,
+    /// Median absolute deviation of all samples from the median.
+    pub mad_ns: f64,
+    pub samples: usize,
+}
+
+impl Timing {
+    /// Summarize the given samples. Returns `None` if there are none.
+    pub fn from_samples(samples: &[Duration]) -> Option<Timing> {
+        let mut nanos = samples
+            .iter()
+            .map(|duration| duration.as_nanos() as f64)
+            .collect::<Vec<_>>();
+        let median_ns = median(&mut nanos)?;
+        let mut deviations = nanos
+            .iter()
+            .map(|value| (value - median_ns).abs())
+            .collect::<Vec<_>>();
+        let mad_ns = median(&mut deviations)?;
+
+        Some(Timing {
+            median_ns,
+            mad_ns,
+            samples: samples.len(),
+        })
+    }
+
+    /// Estimated standard deviation of the samples.
+    fn spread_ns(&self) -> f64 {
+        self.mad_ns * MAD_SCALE
+    }
+}
+
+fn median(values: &mut [f64]) -> Option<f64> {
+    if values.is_empty() {
+        return None;
+    }
+    values.sort_by(f64::total_cmp);
+    let middle = values.len() / 2;
+    if values.len().is_multiple_of(2) {
+        Some((values[middle - 1] + values[middle]) / 2.0)
+    } else {
+        Some(values[middle])
+    }
+}
+
+/// Timings of both phases of a single part.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct Entry {
+    pub day: u8,
+    pub part: u8,
+    pub generator: Timing,
+    pub solver: Timing,
+}
+
+impl Entry {
+    /// Run a part `iterations` times and summarize the timings of its phases.
+    pub fn measure(
+        day: u8,
+        part: Part,
+        input: &str,
+        iterations: usize,
+    ) -> Result<Entry, SolveError> {
+        let mut generator = vec![];
+        let mut solver = vec![];
+        for _ in 0..iterations.max(1) {
+            let run = solver::run(day, part, input)?;
+            generator.push(run.generator.duration);
+            solver.push(run.solver.duration);
+        }
+
+        Ok(Entry {
+            day,
+            part: part.number(),
+            generator: Timing::from_samples(&generator).expect("at least one iteration"),
+            solver: Timing::from_samples(&solver).expect("at least one iteration"),
+        })
+    }
+}
+
+/// Timings of all measured parts, as stored in a baseline file.
+#[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Baseline {
+    pub entries: Vec<Entry>,
+}
+
+impl Baseline {
+    /// Parse a baseline from its JSON representation.
+    pub fn from_json(json: &str) -> serde_json::Result<Baseline> {
+        serde_json::from_str(json)
+    }
+
+    /// Convert this baseline into pretty-printed JSON.
+    pub fn to_json(&self) -> String {
+        serde_json::to_string_pretty(self).expect("baselines are always serializable")
+    }
+
+    fn get(&self, day: u8, part: u8) -> Option<&Entry> {
+        self.entries
+            .iter()
+            .find(|entry| entry.day == day && entry.part == part)
+    }
+
+    /// Parts of the current timings which are not in this baseline and can not be compared.
+    pub fn missing(&self, current: &[Entry]) -> Vec<Missing> {
+        current
+            .iter()
+            .filter(|entry| self.get(entry.day, entry.part).is_none())
+            .map(|entry| Missing {
+                day: entry.day,
+                part: entry.part,
+            })
+            .collect()
+    }
+
+    /// Compare the current timings against this baseline.
+    ///
+    /// A phase regressed if its median is more than `tolerance_percent` slower than in the
+    /// baseline and the slowdown is not explainable by the spread of the measurements. Parts
+    /// without a baseline are skipped, see `missing`.
+    pub fn compare(&self, current: &[Entry], tolerance_percent: f64) -> Vec<Comparison> {
+        let mut comparisons = vec![];
+        for entry in current {
+            let Some(baseline) = self.get(entry.day, entry.part) else {
+                continue;
+            };
+            for (phase, baseline, current) in [
+                (Phase::Generator, baseline.generator, entry.generator),
+                (Phase::Solver, baseline.solver, entry.solver),
+            ] {
+                comparisons.push(Comparison::new(
+                    entry.day,
+                    entry.part,
+                    phase,
+                    baseline,
+                    current,
+                    tolerance_percent,
+                ));
+            }
+        }
+        comparisons
+    }
+}
+
+/// A part which was measured but has no baseline.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct Missing {
+    pub day: u8,
+    pub part: u8,
+}
+
+impl fmt::Display for Missing {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Day {} - Part {} has no baseline", self.day, self.part)
+    }
+}
+
+/// Phase of a part.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Phase {
+    Generator,
+    Solver,
+}
+
+impl fmt::Display for Phase {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.pad(match self {
+            Phase::Generator => "generator",
+            Phase::Solver => "solver",
+        })
+    }
+}
+
+/// Result of comparing the timing of a phase against the baseline.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+pub struct Comparison {
+    pub day: u8,
+    pub part: u8,
+    pub phase: Phase,
+    pub baseline: Timing,
+    pub current: Timing,
+    /// Relative change of the median, positive if the phase got slower.
+    pub change_percent: f64,
+    pub regressed: bool,
+}
+
+impl Comparison {
+    fn new(
+        day: u8,
+        part: u8,
+        phase: Phase,
+        baseline: Timing,
+        current: Timing,
+        tolerance_percent: f64,
+    ) -> Comparison {
+        let slowdown = current.median_ns - baseline.median_ns;
+        let change_percent = if baseline.median_ns > 0.0 {
+            slowdown / baseline.median_ns * 100.0
+        } else {
+            0.0
+        };
+        let noise = NOISE_THRESHOLD * baseline.spread_ns().hypot(current.spread_ns());
+
+        Comparison {
+            day,
+            part,
+            phase,
+            baseline,
+            current,
+            change_percent,
+            regressed: change_percent > tolerance_percent && slowdown > noise,
+        }
+    }
+}
+
+impl fmt::Display for Comparison {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "Day {} - Part {} {:<9} {:>10.1?} -> {:>10.1?} ({:+.1}%){}",
+            self.day,
+            self.part,
+            self.phase,
+            Duration::from_nanos(self.baseline.median_ns as u64),
+            Duration::from_nanos(self.current.median_ns as u64),
+            self.change_percent,
+            if self.regressed { " REGRESSED" } else { "" }
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn timing(median_ns: f64, mad_ns: f64) -> Timing {
+        Timing {
+            median_ns,
+            mad_ns,
+            samples: 10,
+        }
+    }
+
+    fn entry(generator: Timing, solver: Timing) -> Entry {
+        Entry {
+            day: 5,
+            part: 2,
+            generator,
+            solver,
+        }
+    }
+
+    #[test]
+    fn test_timing_from_samples() {
+        let samples = [10, 12, 11, 50, 9].map(Duration::from_nanos);
+        assert_eq!(
+            Timing::from_samples(&samples),
+            Some(Timing {
+                median_ns: 11.0,
+                mad_ns: 1.0,
+                samples: 5
+            })
+        );
+        assert_eq!(Timing::from_samples(&[]), None);
+    }
+
+    #[test]
+    fn test_baseline_compare() {
+        let baseline = Baseline {
+            entries: vec![entry(timing(1000.0, 10.0), timing(1000.0, 10.0))],
+        };
+        let current = [entry(timing(1050.0, 10.0), timing(2000.0, 10.0))];
+
+        let comparisons = baseline.compare(&current, 10.0);
+        assert_eq!(comparisons.len(), 2);
+        assert!(!comparisons[0].regressed);
+        assert!(comparisons[1].regressed);
+        assert_eq!(comparisons[1].change_percent, 100.0);
+    }
+
+    #[test]
+    fn test_baseline_missing() {
+        let baseline = Baseline {
+            entries: vec![entry(timing(1000.0, 10.0), timing(1000.0, 10.0))],
+        };
+        let mut other = entry(timing(1000.0, 10.0), timing(1000.0, 10.0));
+        other.day = 6;
+        let current = [entry(timing(1000.0, 10.0), timing(1000.0, 10.0)), other];
+
+        assert_eq!(baseline.compare(&current, 10.0).len(), 2);
+        assert_eq!(
+            baseline.missing(&current),
+            vec![Missing { day: 6, part: 2 }]
+        );
+        assert_eq!(
+            baseline.missing(&current)[0].to_string(),
+            "Day 6 - Part 2 has no baseline"
+        );
+    }
+
+    #[test]
+    fn test_baseline_compare_noisy() {
+        let baseline = Baseline {
+            entries: vec![entry(timing(1000.0, 200.0), timing(1000.0, 10.0))],
+        };
+        let current = [entry(timing(1500.0, 200.0), timing(1000.0, 10.0))];
+
+        assert!(!baseline.compare(&current, 10.0)[0].regressed);
+    }
+
+    #[test]
+    fn test_baseline_json_roundtrip() {
+        let baseline = Baseline {
+            entries: vec![entry(timing(1000.0, 10.0), timing(20.5, 1.0))],
+        };
+        assert_eq!(Baseline::from_json(&baseline.to_json()).unwrap(), baseline);
+    }
+}
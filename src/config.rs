@@ -0,0 +1,314 @@
+//! Configuration of the runner, read from an `aoc.toml` file.
+
+use std::{
+    env, error, fmt, fs, io,
+    path::{Path, PathBuf},
+    str::FromStr,
+};
+
+use serde::Deserialize;
+
+/// Name of the configuration file.
+pub const FILE_NAME: &str = "aoc.toml";
+
+/// Configuration of the runner.
+///
+/// All directories are relative to the directory containing the configuration file.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Config {
+    pub year: u16,
+    /// Directory with the puzzle inputs (`day<N>.txt`), defaults to `input/<year>`.
+    input_dir: Option<PathBuf>,
+    /// Directory with the example inputs (`day<N>.txt`), defaults to `examples/<year>`.
+    example_dir: Option<PathBuf>,
+    /// Directory with the known answers (`day<N>.txt`, one line per part), defaults to
+    /// `answers/<year>`.
+    answer_dir: Option<PathBuf>,
+    pub session: SessionSource,
+    pub format: OutputFormat,
+    pub color: ColorChoice,
+    /// How often every part is run when measuring its timings.
+    pub iterations: usize,
+    #[serde(skip)]
+    root: PathBuf,
+    #[serde(skip)]
+    file: Option<PathBuf>,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Config {
+            year: 2022,
+            input_dir: None,
+            example_dir: None,
+            answer_dir: None,
+            session: SessionSource::default(),
+            format: OutputFormat::Table,
+            color: ColorChoice::Auto,
+            iterations: 10,
+            root: PathBuf::from("."),
+            file: None,
+        }
+    }
+}
+
+impl Config {
+    /// Parse a configuration whose relative directories are resolved against `root`.
+    pub fn from_toml(toml: &str, root: &Path) -> Result<Config, toml::de::Error> {
+        let mut config = toml::from_str::<Config>(toml)?;
+        config.root = root.to_path_buf();
+        Ok(config)
+    }
+
+    /// Load the configuration from the given file.
+    pub fn load(path: &Path) -> Result<Config, ConfigError> {
+        let content = fs::read_to_string(path).map_err(|error| ConfigError::Io {
+            path: path.to_path_buf(),
+            error,
+        })?;
+        let root = path.parent().unwrap_or_else(|| Path::new("."));
+        let mut config = Config::from_toml(&content, root).map_err(|error| ConfigError::Parse {
+            path: path.to_path_buf(),
+            error,
+        })?;
+        config.file = Some(path.to_path_buf());
+        Ok(config)
+    }
+
+    /// Load the first `aoc.toml` found in `start` or any of its parents.
+    ///
+    /// Without any configuration file, the defaults relative to `start` are used.
+    pub fn discover(start: &Path) -> Result<Config, ConfigError> {
+        for dir in start.ancestors() {
+            let path = dir.join(FILE_NAME);
+            if path.is_file() {
+                return Config::load(&path);
+            }
+        }
+
+        Ok(Config {
+            root: start.to_path_buf(),
+            ..Config::default()
+        })
+    }
+
+    fn dir(&self, configured: &Option<PathBuf>, default: &str) -> PathBuf {
+        match configured {
+            Some(dir) => self.root.join(dir),
+            None => self.root.join(default).join(self.year.to_string()),
+        }
+    }
+
+    pub fn input_dir(&self) -> PathBuf {
+        self.dir(&self.input_dir, "input")
+    }
+
+    pub fn example_dir(&self) -> PathBuf {
+        self.dir(&self.example_dir, "examples")
+    }
+
+    pub fn answer_dir(&self) -> PathBuf {
+        self.dir(&self.answer_dir, "answers")
+    }
+
+    /// Path of the file this configuration was loaded from.
+    pub fn file(&self) -> Option<&Path> {
+        self.file.as_deref()
+    }
+
+    /// Path of the file the session token is read from, if it comes from a file.
+    pub fn session_file(&self) -> Option<PathBuf> {
+        match &self.session {
+            SessionSource::File(path) => Some(self.root.join(path)),
+            _ => None,
+        }
+    }
+
+    /// Get the session token, if there is one.
+    pub fn session_token(&self) -> Result<Option<String>, ConfigError> {
+        let token = match &self.session {
+            SessionSource::Env(var) => env::var(var).ok(),
+            SessionSource::File(path) => {
+                let path = self.root.join(path);
+                let token =
+                    fs::read_to_string(&path).map_err(|error| ConfigError::Io { path, error })?;
+                Some(token)
+            }
+            SessionSource::Token(token) => Some(token.clone()),
+        };
+        Ok(token
+            .map(|token| token.trim().to_string())
+            .filter(|token| !token.is_empty()))
+    }
+}
+
+/// Where the session token for adventofcode.com comes from.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase", deny_unknown_fields)]
+pub enum SessionSource {
+    /// Read the token from an environment variable.
+    Env(String),
+    /// Read the token from a file, relative to the configuration file.
+    File(PathBuf),
+    /// Use the token as it is written in the configuration.
+    Token(String),
+}
+
+impl Default for SessionSource {
+    fn default() -> Self {
+        SessionSource::Env("AOC_SESSION".to_string())
+    }
+}
+
+/// Format of the output of the runner.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum OutputFormat {
+    #[default]
+    Table,
+    Json,
+}
+
+impl FromStr for OutputFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "table" => Ok(OutputFormat::Table),
+            "json" => Ok(OutputFormat::Json),
+            _ => Err(format!("unknown format '{s}' (expected table or json)")),
+        }
+    }
+}
+
+/// Whether the output of the runner is colored.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ColorChoice {
+    /// Color the output if it is written to a terminal and `NO_COLOR` is not set.
+    #[default]
+    Auto,
+    Always,
+    Never,
+}
+
+impl FromStr for ColorChoice {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "auto" => Ok(ColorChoice::Auto),
+            "always" => Ok(ColorChoice::Always),
+            "never" => Ok(ColorChoice::Never),
+            _ => Err(format!(
+                "unknown color choice '{s}' (expected auto, always or never)"
+            )),
+        }
+    }
+}
+
+/// Error while reading the configuration.
+#[derive(Debug)]
+pub enum ConfigError {
+    Io {
+        path: PathBuf,
+        error: io::Error,
+    },
+    Parse {
+        path: PathBuf,
+        error: toml::de::Error,
+    },
+}
+
+impl fmt::Display for ConfigError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ConfigError::Io { path, error } => {
+                write!(f, "could not read {}: {error}", path.display())
+            }
+            ConfigError::Parse { path, error } => {
+                write!(f, "invalid configuration in {}: {error}", path.display())
+            }
+        }
+    }
+}
+
+impl error::Error for ConfigError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_config_defaults() {
+        let config = Config::from_toml("", Path::new("/aoc")).unwrap();
+        assert_eq!(config.year, 2022);
+        assert_eq!(config.input_dir(), PathBuf::from("/aoc/input/2022"));
+        assert_eq!(config.example_dir(), PathBuf::from("/aoc/examples/2022"));
+        assert_eq!(config.answer_dir(), PathBuf::from("/aoc/answers/2022"));
+        assert_eq!(
+            config.session,
+            SessionSource::Env("AOC_SESSION".to_string())
+        );
+        assert_eq!(config.iterations, 10);
+    }
+
+    #[test]
+    fn test_config_from_toml() {
+        let config = Config::from_toml(
+            r#"
+            year = 2021
+            input_dir = "inputs"
+            format = "json"
+            color = "never"
+            iterations = 3
+            session = { token = " abc\n" }
+            "#,
+            Path::new("/aoc"),
+        )
+        .unwrap();
+
+        assert_eq!(config.input_dir(), PathBuf::from("/aoc/inputs"));
+        assert_eq!(config.answer_dir(), PathBuf::from("/aoc/answers/2021"));
+        assert_eq!(config.format, OutputFormat::Json);
+        assert_eq!(config.color, ColorChoice::Never);
+        assert_eq!(config.iterations, 3);
+        assert_eq!(config.session_token().unwrap(), Some("abc".to_string()));
+    }
+
+    #[test]
+    fn test_config_unknown_key() {
+        assert!(Config::from_toml("years = 2021", Path::new(".")).is_err());
+    }
+
+    #[test]
+    fn test_config_session_file() {
+        let dir = env::temp_dir().join(format!("aoc-session-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(dir.join(".session"), "secret\n").unwrap();
+
+        let config = Config::from_toml("session = { file = \".session\" }", &dir).unwrap();
+        let token = config.session_token();
+        fs::remove_dir_all(&dir).unwrap();
+
+        assert_eq!(config.session_file(), Some(dir.join(".session")));
+        assert_eq!(token.unwrap(), Some("secret".to_string()));
+        assert!(config.session_token().is_err());
+    }
+
+    #[test]
+    fn test_config_discover() {
+        let dir = env::temp_dir().join(format!("aoc-config-{}", std::process::id()));
+        let nested = dir.join("a").join("b");
+        fs::create_dir_all(&nested).unwrap();
+        fs::write(dir.join(FILE_NAME), "year = 2020").unwrap();
+
+        let config = Config::discover(&nested).unwrap();
+        fs::remove_dir_all(&dir).unwrap();
+
+        assert_eq!(config.year, 2020);
+        assert_eq!(config.file(), Some(dir.join(FILE_NAME).as_path()));
+        assert_eq!(config.input_dir(), dir.join("input").join("2020"));
+    }
+}
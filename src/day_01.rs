@@ -1,36 +1,134 @@
+use std::fmt;
+
 use aoc_runner_derive::{aoc, aoc_generator};
 
-type Elve = i64;
+use crate::{
+    solver::{Part, Run},
+    top_k::{top_k, Ranked},
+};
+use checked::{CalorieError, Calories};
 
-#[aoc_generator(day01)]
-fn generator_day01(inp: &str) -> Vec<Elve> {
-    let mut elves = vec![];
-    let mut current_elve = 0;
-    for val in inp.lines() {
-        if val.trim() == "" {
-            elves.push(current_elve);
-            current_elve = 0;
-            continue;
+pub mod checked;
+pub mod partition;
+pub mod stats;
+pub mod stream;
+
+/// An elf together with the calories of all food items it carries.
+///
+/// The total is accumulated in `T`, see `try_generator_day01_wide` for totals beyond `i64`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Elf<T = i64> {
+    /// Position of this elf in the input, starting at 0.
+    pub index: usize,
+    pub items: Vec<i64>,
+    pub total: T,
+}
+
+/// Elves with the most calories and the sum of their calories.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Leaders {
+    /// Sum of the calories of the elves. Every item fits into an `i64`, so this only overflows
+    /// after more than 2^63 items.
+    pub total: i128,
+    /// Indices of the elves, the one carrying the most first.
+    pub elves: Vec<usize>,
+}
+
+impl Leaders {
+    /// Get the `k` elves carrying the most calories.
+    pub fn of<T: Calories>(elves: &[Elf<T>], k: usize) -> Leaders {
+        Leaders::from_ranked(&top_k(elves.iter().map(|elf| elf.total), k))
+    }
+
+    /// Build the leaders from the totals of the elves, indexed by their position in the input.
+    fn from_ranked<T: Calories>(top: &[Ranked<T>]) -> Leaders {
+        Leaders {
+            total: top.iter().map(|elf| elf.value.into()).sum(),
+            elves: top.iter().map(|elf| elf.index).collect(),
         }
-        current_elve += val.parse::<i64>().unwrap();
     }
-    elves.push(current_elve);
+}
+
+impl fmt::Display for Leaders {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        // elves are numbered from 1 for humans
+        let numbers = self
+            .elves
+            .iter()
+            .map(|index| (index + 1).to_string())
+            .collect::<Vec<_>>();
+        let noun = if numbers.len() == 1 { "elf" } else { "elves" };
+        write!(f, "{} ({noun} {})", self.total, numbers.join(", "))
+    }
+}
+
+/// Get the elf carrying the most calories.
+pub fn carrying_most(elves: &[Elf]) -> Option<&Elf> {
+    let index = top_k(elves.iter().map(|elf| elf.total), 1).first()?.index;
+    Some(&elves[index])
+}
+
+/// Get all elves which carry at least one item with more than the given calories.
+pub fn with_item_above(elves: &[Elf], calories: i64) -> Vec<&Elf> {
     elves
+        .iter()
+        .filter(|elf| elf.items.iter().any(|&item| item > calories))
+        .collect()
+}
+
+/// Get all elves which carry the highest number of items.
+pub fn with_most_items(elves: &[Elf]) -> Vec<&Elf> {
+    let most = elves.iter().map(|elf| elf.items.len()).max().unwrap_or(0);
+    elves.iter().filter(|elf| elf.items.len() == most).collect()
+}
+
+/// Parse the puzzle input into elves.
+///
+/// Panics with the offending line if the input is invalid, see `try_generator_day01`.
+#[aoc_generator(day01)]
+pub fn generator_day01(inp: &str) -> Vec<Elf> {
+    try_generator_day01(inp).unwrap_or_else(|error| panic!("invalid input: {error}"))
+}
+
+/// Parse the puzzle input into elves, rejecting negative calories and overflowing totals.
+pub fn try_generator_day01(inp: &str) -> Result<Vec<Elf>, CalorieError> {
+    Ok(to_elves(checked::parse(inp)?))
+}
+
+/// Parse the puzzle input into elves whose totals are accumulated in an `i128`, for inputs whose
+/// totals do not fit into an `i64`.
+pub fn try_generator_day01_wide(inp: &str) -> Result<Vec<Elf<i128>>, CalorieError> {
+    Ok(to_elves(checked::parse_wide(inp)?))
+}
+
+fn to_elves<T>(parsed: Vec<(Vec<i64>, T)>) -> Vec<Elf<T>> {
+    parsed
+        .into_iter()
+        .enumerate()
+        .map(|(index, (items, total))| Elf {
+            index,
+            items,
+            total,
+        })
+        .collect()
 }
 
 #[aoc(day1, part1)]
-pub fn day01_part1(elves: &[Elve]) -> i64 {
-    let mut elves = elves.to_vec();
-    elves.sort();
-    *elves.last().unwrap()
+pub fn day01_part1(elves: &[Elf]) -> Leaders {
+    Leaders::of(elves, 1)
 }
 
 #[aoc(day1, part2)]
-pub fn day01_part2(elves: &[Elve]) -> i64 {
-    let mut elves = elves.to_vec();
-    elves.sort();
-    let last_three_elves = elves.split_off(elves.len() - 3);
-    last_three_elves.iter().sum()
+pub fn day01_part2(elves: &[Elf]) -> Leaders {
+    Leaders::of(elves, 3)
+}
+
+/// Run the given part of this day for the provided input.
+pub(crate) fn run(part: Part, input: &str) -> Run {
+    match part {
+        Part::One => Run::measure(|| generator_day01(input), |elves| day01_part1(elves)),
+        Part::Two => Run::measure(|| generator_day01(input), |elves| day01_part2(elves)),
+    }
 }
 
 #[cfg(test)]
@@ -55,7 +153,14 @@ mod tests {
 
 10000",
         );
-        assert_eq!(day01_part1(&elves), 24000)
+        assert_eq!(
+            day01_part1(&elves),
+            Leaders {
+                total: 24000,
+                elves: vec![3]
+            }
+        );
+        assert_eq!(day01_part1(&elves).to_string(), "24000 (elf 4)");
     }
 
     #[test]
@@ -76,6 +181,76 @@ mod tests {
 
 10000",
         );
-        assert_eq!(day01_part2(&elves), 45000)
+        assert_eq!(
+            day01_part2(&elves),
+            Leaders {
+                total: 45000,
+                elves: vec![3, 2, 4]
+            }
+        );
+        assert_eq!(day01_part2(&elves).to_string(), "45000 (elves 4, 3, 5)");
+    }
+
+    #[test]
+    fn test_generator_wide() {
+        let inp = "9223372036854775807\n9223372036854775807\n\n1";
+        assert!(try_generator_day01(inp).is_err());
+
+        let elves = try_generator_day01_wide(inp).unwrap();
+        assert_eq!(elves[0].total, 2 * i128::from(i64::MAX));
+        assert_eq!(
+            Leaders::of(&elves, 2),
+            Leaders {
+                total: 2 * i128::from(i64::MAX) + 1,
+                elves: vec![0, 1]
+            }
+        );
+    }
+
+    #[test]
+    fn test_leaders_wide_total() {
+        let elves = generator_day01("9223372036854775807\n\n9223372036854775807");
+        assert_eq!(day01_part2(&elves).total, 2 * i128::from(i64::MAX));
+    }
+
+    #[test]
+    fn test_generator_day01() {
+        let elves = generator_day01("1000\n2000\n\n4000");
+        assert_eq!(
+            elves,
+            vec![
+                Elf {
+                    index: 0,
+                    items: vec![1000, 2000],
+                    total: 3000
+                },
+                Elf {
+                    index: 1,
+                    items: vec![4000],
+                    total: 4000
+                }
+            ]
+        );
+    }
+
+    #[test]
+    fn test_elf_queries() {
+        let elves = generator_day01("1000\n2000\n3000\n\n4000\n\n5000\n6000\n\n100\n200\n300");
+        assert_eq!(carrying_most(&elves).map(|elf| elf.index), Some(2));
+        assert_eq!(carrying_most(&[]), None);
+        assert_eq!(
+            with_item_above(&elves, 3500)
+                .iter()
+                .map(|elf| elf.index)
+                .collect::<Vec<_>>(),
+            vec![1, 2]
+        );
+        assert_eq!(
+            with_most_items(&elves)
+                .iter()
+                .map(|elf| elf.index)
+                .collect::<Vec<_>>(),
+            vec![0, 3]
+        );
     }
 }
@@ -0,0 +1,185 @@
+use std::{error::Error, fmt, num::IntErrorKind};
+
+/// Integer type the total calories of an elf are accumulated in.
+///
+/// Every item fits into an `i64`, but `i128` can be used for inputs whose totals do not.
+pub trait Calories: Copy + Ord + From<i64> + Into<i128> {
+    const ZERO: Self;
+
+    fn checked_add(self, other: Self) -> Option<Self>;
+}
+
+impl Calories for i64 {
+    const ZERO: Self = 0;
+
+    fn checked_add(self, other: Self) -> Option<Self> {
+        i64::checked_add(self, other)
+    }
+}
+
+impl Calories for i128 {
+    const ZERO: Self = 0;
+
+    fn checked_add(self, other: Self) -> Option<Self> {
+        i128::checked_add(self, other)
+    }
+}
+
+/// What is wrong with a line of the puzzle input.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CalorieErrorKind {
+    /// The line is neither empty nor a number.
+    Invalid(String),
+    Negative(String),
+    /// The number of the line does not fit into an `i64`.
+    TooLarge(String),
+    /// The total of the elf does not fit into the accumulator after adding this line.
+    Overflow,
+}
+
+/// Error in a line (counted from 1) of the puzzle input.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CalorieError {
+    pub line: usize,
+    pub kind: CalorieErrorKind,
+}
+
+impl fmt::Display for CalorieError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.kind {
+            CalorieErrorKind::Invalid(text) => {
+                write!(f, "line {}: '{text}' is no number", self.line)
+            }
+            CalorieErrorKind::Negative(text) => {
+                write!(f, "line {}: {text} calories are negative", self.line)
+            }
+            CalorieErrorKind::TooLarge(text) => {
+                write!(f, "line {}: {text} calories are too large", self.line)
+            }
+            CalorieErrorKind::Overflow => {
+                write!(f, "line {}: total calories of the elf overflow", self.line)
+            }
+        }
+    }
+}
+
+impl Error for CalorieError {}
+
+/// Parse the puzzle input into the items and the total of every elf.
+///
+/// Every line is checked, so negative calories and overflowing totals are reported instead of
+/// wrapping silently.
+pub fn parse(inp: &str) -> Result<Vec<(Vec<i64>, i64)>, CalorieError> {
+    parse_as(inp)
+}
+
+/// Parse the puzzle input like `parse`, but accumulate the totals in an `i128`.
+pub fn parse_wide(inp: &str) -> Result<Vec<(Vec<i64>, i128)>, CalorieError> {
+    parse_as(inp)
+}
+
+fn parse_as<T: Calories>(inp: &str) -> Result<Vec<(Vec<i64>, T)>, CalorieError> {
+    let mut elves = vec![];
+    let mut items = vec![];
+    let mut total = T::ZERO;
+    for (i, val) in inp.lines().enumerate() {
+        let line = i + 1;
+        let val = val.trim();
+        if val.is_empty() {
+            elves.push((items, total));
+            items = vec![];
+            total = T::ZERO;
+            continue;
+        }
+
+        let item = parse_item(val).map_err(|kind| CalorieError { line, kind })?;
+        total = total.checked_add(T::from(item)).ok_or(CalorieError {
+            line,
+            kind: CalorieErrorKind::Overflow,
+        })?;
+        items.push(item);
+    }
+    elves.push((items, total));
+    Ok(elves)
+}
+
+/// Parse the calories of a single, non-empty line.
+pub(super) fn parse_item(val: &str) -> Result<i64, CalorieErrorKind> {
+    match val.parse() {
+        Ok(item) if item < 0 => Err(CalorieErrorKind::Negative(val.to_string())),
+        Ok(item) => Ok(item),
+        Err(error) => Err(match error.kind() {
+            IntErrorKind::PosOverflow => CalorieErrorKind::TooLarge(val.to_string()),
+            IntErrorKind::NegOverflow => CalorieErrorKind::Negative(val.to_string()),
+            _ => CalorieErrorKind::Invalid(val.to_string()),
+        }),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse() {
+        assert_eq!(
+            parse("1\n2\n\n3\n").unwrap(),
+            vec![(vec![1, 2], 3), (vec![3], 3)]
+        );
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        assert_eq!(
+            parse("1\n\nabc"),
+            Err(CalorieError {
+                line: 3,
+                kind: CalorieErrorKind::Invalid("abc".to_string())
+            })
+        );
+        assert_eq!(
+            parse("1\n-5").unwrap_err().kind,
+            CalorieErrorKind::Negative("-5".to_string())
+        );
+        assert_eq!(
+            parse("99999999999999999999").unwrap_err().kind,
+            CalorieErrorKind::TooLarge("99999999999999999999".to_string())
+        );
+        assert_eq!(
+            parse("1\n\n9223372036854775807\n1").unwrap_err(),
+            CalorieError {
+                line: 4,
+                kind: CalorieErrorKind::Overflow
+            }
+        );
+    }
+
+    #[test]
+    fn test_parse_wide() {
+        assert_eq!(
+            parse_wide("9223372036854775807\n1\n\n2").unwrap(),
+            vec![(vec![i64::MAX, 1], i128::from(i64::MAX) + 1), (vec![2], 2)]
+        );
+        // the items themselves still have to fit into an `i64`
+        assert_eq!(
+            parse_wide("99999999999999999999").unwrap_err().kind,
+            CalorieErrorKind::TooLarge("99999999999999999999".to_string())
+        );
+    }
+
+    #[test]
+    fn test_display_error() {
+        assert_eq!(
+            CalorieError {
+                line: 2,
+                kind: CalorieErrorKind::Overflow
+            }
+            .to_string(),
+            "line 2: total calories of the elf overflow"
+        );
+        assert_eq!(
+            parse("1\n-5").unwrap_err().to_string(),
+            "line 2: -5 calories are negative"
+        );
+    }
+}
@@ -0,0 +1,339 @@
+use std::{
+    cmp::Reverse,
+    collections::{BinaryHeap, HashMap},
+    fmt,
+};
+
+use super::Elf;
+
+/// Largest number of weights `Strategy::Auto` solves exactly.
+pub const EXACT_LIMIT: usize = 12;
+
+/// Algorithm used to split weights into teams.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Strategy {
+    /// Optimal partition, but exponential in the number of weights.
+    Exact,
+    /// Assign the heaviest remaining weight to the lightest team.
+    Greedy,
+    /// Multi-way Karmarkar-Karp differencing, usually better than `Greedy` for many weights.
+    KarmarkarKarp,
+    /// `Exact` for up to `EXACT_LIMIT` weights and `KarmarkarKarp` otherwise.
+    Auto,
+}
+
+/// A team and the indices of its members.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct Team {
+    pub members: Vec<usize>,
+    pub total: i128,
+}
+
+/// Split of weights into teams.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Partition {
+    pub teams: Vec<Team>,
+}
+
+impl Partition {
+    /// Difference between the heaviest and the lightest team.
+    pub fn imbalance(&self) -> i128 {
+        let totals = self.teams.iter().map(|team| team.total);
+        totals.clone().max().unwrap_or(0) - totals.min().unwrap_or(0)
+    }
+
+    /// Build a partition from the team every weight has been assigned to.
+    fn from_assignment(weights: &[i128], k: usize, assignment: &[usize]) -> Partition {
+        let mut teams = vec![Team::default(); k];
+        for (index, &team) in assignment.iter().enumerate() {
+            teams[team].members.push(index);
+            teams[team].total += weights[index];
+        }
+        Partition { teams }
+    }
+}
+
+impl fmt::Display for Partition {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (i, team) in self.teams.iter().enumerate() {
+            // members are numbered from 1 for humans
+            let members = team
+                .members
+                .iter()
+                .map(|member| (member + 1).to_string())
+                .collect::<Vec<_>>();
+            writeln!(f, "team {}: {} ({})", i + 1, team.total, members.join(", "))?;
+        }
+        writeln!(f, "imbalance: {}", self.imbalance())
+    }
+}
+
+/// Split the elves into `k` teams carrying as equal amounts of calories as possible.
+///
+/// Members of the teams are the indices of the elves.
+pub fn partition_elves(elves: &[Elf], k: usize, strategy: Strategy) -> Partition {
+    let weights = elves.iter().map(|elf| elf.total).collect::<Vec<_>>();
+    let mut partition = balance(&weights, k, strategy);
+    for team in &mut partition.teams {
+        for member in &mut team.members {
+            *member = elves[*member].index;
+        }
+    }
+    partition
+}
+
+/// Split `weights` into `k` teams whose sums are as equal as possible.
+///
+/// Members of the teams are the indices of the weights.
+pub fn balance(weights: &[i64], k: usize, strategy: Strategy) -> Partition {
+    let k = k.max(1);
+    // sums of many weights up to `i64::MAX` do not fit into an `i64`
+    let weights = weights
+        .iter()
+        .map(|&weight| i128::from(weight))
+        .collect::<Vec<_>>();
+    let assignment = match strategy {
+        Strategy::Exact => exact(&weights, k),
+        Strategy::Greedy => greedy(&weights, k),
+        Strategy::KarmarkarKarp => karmarkar_karp(&weights, k),
+        Strategy::Auto if weights.len() <= EXACT_LIMIT => exact(&weights, k),
+        Strategy::Auto => karmarkar_karp(&weights, k),
+    };
+    Partition::from_assignment(&weights, k, &assignment)
+}
+
+/// Assign the weights in descending order, always to the currently lightest team.
+fn greedy(weights: &[i128], k: usize) -> Vec<usize> {
+    let mut order = (0..weights.len()).collect::<Vec<_>>();
+    order.sort_by_key(|&index| Reverse(weights[index]));
+
+    let mut teams = (0..k)
+        .map(|team| Reverse((0, team)))
+        .collect::<BinaryHeap<_>>();
+    let mut assignment = vec![0; weights.len()];
+    for index in order {
+        let Reverse((total, team)) = teams.pop().expect("there is at least one team");
+        assignment[index] = team;
+        teams.push(Reverse((total + weights[index], team)));
+    }
+    assignment
+}
+
+/// A partial partition of the multi-way Karmarkar-Karp heuristic.
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord)]
+struct Differenced {
+    /// Difference between the heaviest and the lightest subset, the heap is ordered by it.
+    spread: i128,
+    /// Sums and members of the subsets, sorted ascending by sum.
+    subsets: Vec<(i128, Vec<usize>)>,
+}
+
+impl Differenced {
+    fn new(mut subsets: Vec<(i128, Vec<usize>)>) -> Differenced {
+        subsets.sort();
+        Differenced {
+            spread: subsets[subsets.len() - 1].0 - subsets[0].0,
+            subsets,
+        }
+    }
+}
+
+/// Repeatedly combine the two partial partitions with the largest spread, joining the heaviest
+/// subset of one with the lightest of the other.
+fn karmarkar_karp(weights: &[i128], k: usize) -> Vec<usize> {
+    let mut heap = weights
+        .iter()
+        .enumerate()
+        .map(|(index, &weight)| {
+            let mut subsets = vec![(0, vec![]); k];
+            subsets[0] = (weight, vec![index]);
+            Differenced::new(subsets)
+        })
+        .collect::<BinaryHeap<_>>();
+
+    while heap.len() > 1 {
+        let first = heap.pop().expect("there are two partitions");
+        let second = heap.pop().expect("there are two partitions");
+        let combined = first
+            .subsets
+            .into_iter()
+            .zip(second.subsets.into_iter().rev())
+            .map(|((left_sum, mut left), (right_sum, right))| {
+                left.extend(right);
+                (left_sum + right_sum, left)
+            })
+            .collect();
+        heap.push(Differenced::new(combined));
+    }
+
+    let mut assignment = vec![0; weights.len()];
+    if let Some(partition) = heap.pop() {
+        for (team, (_, members)) in partition.subsets.into_iter().enumerate() {
+            for member in members {
+                assignment[member] = team;
+            }
+        }
+    }
+    assignment
+}
+
+/// States of the exact solver after assigning a number of weights, mapped to the previous state
+/// and the position of the team in it the weight was added to.
+type Layer = HashMap<Vec<i128>, (Vec<i128>, usize)>;
+
+/// Find an optimal partition by dynamic programming over all reachable team sums.
+///
+/// States are the sorted sums of the teams, so permutations of the teams are only visited once.
+/// For every state, the transition which reached it first is kept to reconstruct the assignment.
+fn exact(weights: &[i128], k: usize) -> Vec<usize> {
+    let mut layers: Vec<Layer> = vec![HashMap::new()];
+    layers[0].insert(vec![0; k], (vec![], 0));
+
+    for &weight in weights {
+        let mut next = HashMap::new();
+        for state in layers[layers.len() - 1].keys() {
+            for position in 0..k {
+                // adding to one of several equal sums leads to the same state
+                if position > 0 && state[position] == state[position - 1] {
+                    continue;
+                }
+                let mut sums = state.clone();
+                sums[position] += weight;
+                sums.sort();
+                next.entry(sums)
+                    .or_insert_with(|| (state.clone(), position));
+            }
+        }
+        layers.push(next);
+    }
+
+    let best = layers[layers.len() - 1]
+        .keys()
+        .min_by_key(|sums| (sums[k - 1] - sums[0], sums[k - 1], (*sums).clone()))
+        .expect("there is at least one state")
+        .clone();
+
+    // walk back through the layers, keeping track of which team sits at which position
+    let mut assignment = vec![0; weights.len()];
+    let mut teams = (0..k).collect::<Vec<_>>();
+    let mut state = best;
+    for index in (0..weights.len()).rev() {
+        let (previous, position) = layers[index + 1][&state].clone();
+
+        let mut updated = previous.clone();
+        updated[position] += weights[index];
+        // positions of `updated` in the order they appear in `state`
+        let mut order = (0..k).collect::<Vec<_>>();
+        order.sort_by_key(|&i| updated[i]);
+
+        let mut previous_teams = vec![0; k];
+        for (sorted, &unsorted) in order.iter().enumerate() {
+            previous_teams[unsorted] = teams[sorted];
+        }
+
+        assignment[index] = previous_teams[position];
+        teams = previous_teams;
+        state = previous;
+    }
+    assignment
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const WEIGHTS: [i64; 7] = [8, 7, 6, 5, 4, 3, 3];
+
+    fn totals(partition: &Partition) -> Vec<i128> {
+        let mut totals = partition
+            .teams
+            .iter()
+            .map(|team| team.total)
+            .collect::<Vec<_>>();
+        totals.sort();
+        totals
+    }
+
+    fn assert_complete(partition: &Partition, n: usize) {
+        let mut members = partition
+            .teams
+            .iter()
+            .flat_map(|team| team.members.iter().copied())
+            .collect::<Vec<_>>();
+        members.sort();
+        assert_eq!(members, (0..n).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn test_exact() {
+        let partition = balance(&WEIGHTS, 3, Strategy::Exact);
+        assert_complete(&partition, WEIGHTS.len());
+        assert_eq!(totals(&partition), vec![12, 12, 12]);
+        assert_eq!(partition.imbalance(), 0);
+
+        for team in &partition.teams {
+            let sum = team
+                .members
+                .iter()
+                .map(|&i| i128::from(WEIGHTS[i]))
+                .sum::<i128>();
+            assert_eq!(sum, team.total);
+        }
+    }
+
+    #[test]
+    fn test_greedy() {
+        let partition = balance(&WEIGHTS, 3, Strategy::Greedy);
+        assert_complete(&partition, WEIGHTS.len());
+        assert_eq!(totals(&partition), vec![11, 11, 14]);
+    }
+
+    #[test]
+    fn test_karmarkar_karp() {
+        let partition = balance(&[8, 7, 6, 5, 4], 2, Strategy::KarmarkarKarp);
+        assert_complete(&partition, 5);
+        assert_eq!(totals(&partition), vec![14, 16]);
+
+        let partition = balance(&WEIGHTS, 3, Strategy::KarmarkarKarp);
+        assert_complete(&partition, WEIGHTS.len());
+        assert!(partition.imbalance() <= 2);
+    }
+
+    #[test]
+    fn test_more_teams_than_weights() {
+        let partition = balance(&[5, 3], 3, Strategy::Auto);
+        assert_eq!(totals(&partition), vec![0, 3, 5]);
+        assert_eq!(partition.imbalance(), 5);
+    }
+
+    #[test]
+    fn test_balance_large_weights() {
+        let max = i128::from(i64::MAX);
+        for strategy in [Strategy::Exact, Strategy::Greedy, Strategy::KarmarkarKarp] {
+            let partition = balance(&[i64::MAX, i64::MAX, 1], 1, strategy);
+            assert_eq!(totals(&partition), vec![2 * max + 1]);
+
+            let partition = balance(&[i64::MAX; 4], 2, strategy);
+            assert_eq!(totals(&partition), vec![2 * max, 2 * max]);
+        }
+    }
+
+    #[test]
+    fn test_partition_elves() {
+        let elves = [6000, 4000, 11000, 24000, 10000]
+            .into_iter()
+            .enumerate()
+            .map(|(index, total)| Elf {
+                index,
+                items: vec![total],
+                total,
+            })
+            .collect::<Vec<_>>();
+        let partition = partition_elves(&elves, 2, Strategy::Auto);
+        assert_eq!(partition.imbalance(), 1000);
+        assert_eq!(
+            partition.to_string(),
+            "team 1: 27000 (1, 3, 5)\nteam 2: 28000 (2, 4)\nimbalance: 1000\n"
+        );
+    }
+}
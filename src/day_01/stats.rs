@@ -0,0 +1,299 @@
+use std::fmt;
+
+use serde::Serialize;
+
+use super::Elf;
+
+/// Width of the longest bar when rendering a histogram.
+const BAR_WIDTH: usize = 40;
+
+/// Descriptive statistics over the calories the elves carry in total.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Statistics {
+    /// Totals together with the index of their elf, sorted ascending by total.
+    totals: Vec<(i64, usize)>,
+}
+
+impl Statistics {
+    /// Collect the statistics of the given elves. Returns `None` if there are no elves.
+    pub fn new(elves: &[Elf]) -> Option<Statistics> {
+        if elves.is_empty() {
+            return None;
+        }
+
+        let mut totals = elves
+            .iter()
+            .map(|elf| (elf.total, elf.index))
+            .collect::<Vec<_>>();
+        totals.sort();
+        Some(Statistics { totals })
+    }
+
+    pub fn count(&self) -> usize {
+        self.totals.len()
+    }
+
+    pub fn min(&self) -> i64 {
+        self.totals[0].0
+    }
+
+    pub fn max(&self) -> i64 {
+        self.totals[self.totals.len() - 1].0
+    }
+
+    pub fn mean(&self) -> f64 {
+        self.totals
+            .iter()
+            .map(|&(total, _)| total as f64)
+            .sum::<f64>()
+            / self.count() as f64
+    }
+
+    pub fn median(&self) -> f64 {
+        self.percentile(50.0)
+    }
+
+    /// Population standard deviation of the totals.
+    pub fn std_dev(&self) -> f64 {
+        let mean = self.mean();
+        let variance = self
+            .totals
+            .iter()
+            .map(|&(total, _)| (total as f64 - mean).powi(2))
+            .sum::<f64>()
+            / self.count() as f64;
+        variance.sqrt()
+    }
+
+    /// Get the given percentile (between 0 and 100), interpolating linearly between the two
+    /// closest totals.
+    pub fn percentile(&self, percent: f64) -> f64 {
+        let rank = percent.clamp(0.0, 100.0) / 100.0 * (self.count() - 1) as f64;
+        let lower = self.totals[rank.floor() as usize].0 as f64;
+        let upper = self.totals[rank.ceil() as usize].0 as f64;
+        lower + (upper - lower) * rank.fract()
+    }
+
+    /// Count the totals in `buckets` buckets of equal width, covering all totals.
+    pub fn histogram(&self, buckets: usize) -> Histogram {
+        // the range of the totals and the end of the last bucket may exceed an `i64`
+        let buckets = buckets.max(1) as i128;
+        let min = i128::from(self.min());
+        let range = i128::from(self.max()) - min + 1;
+        let width = (range + buckets - 1) / buckets;
+
+        let mut histogram = (0..buckets)
+            .map(|i| Bucket {
+                start: min + i * width,
+                end: min + (i + 1) * width,
+                count: 0,
+            })
+            .collect::<Vec<_>>();
+        for &(total, _) in &self.totals {
+            histogram[((i128::from(total) - min) / width) as usize].count += 1;
+        }
+        Histogram(histogram)
+    }
+
+    /// Get the indices of all elves whose total lies outside of Tukey's fences, i.e. more than
+    /// 1.5 interquartile ranges below the first or above the third quartile.
+    pub fn outliers(&self) -> Vec<usize> {
+        let q1 = self.percentile(25.0);
+        let q3 = self.percentile(75.0);
+        let fence = 1.5 * (q3 - q1);
+
+        let mut outliers = self
+            .totals
+            .iter()
+            .filter(|&&(total, _)| (total as f64) < q1 - fence || (total as f64) > q3 + fence)
+            .map(|&(_, index)| index)
+            .collect::<Vec<_>>();
+        outliers.sort();
+        outliers
+    }
+
+    /// Compile a report with the given percentiles and number of histogram buckets.
+    pub fn report(&self, percentiles: &[f64], buckets: usize) -> Report {
+        Report {
+            count: self.count(),
+            min: self.min(),
+            max: self.max(),
+            mean: self.mean(),
+            median: self.median(),
+            std_dev: self.std_dev(),
+            percentiles: percentiles
+                .iter()
+                .map(|&percent| (percent, self.percentile(percent)))
+                .collect(),
+            histogram: self.histogram(buckets),
+            outliers: self.outliers(),
+        }
+    }
+}
+
+/// Number of totals within `start..end`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct Bucket {
+    pub start: i128,
+    pub end: i128,
+    pub count: usize,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Histogram(pub Vec<Bucket>);
+
+impl fmt::Display for Histogram {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let most = self.0.iter().map(|bucket| bucket.count).max().unwrap_or(0);
+        let width = self
+            .0
+            .iter()
+            .map(|bucket| bucket.end.to_string().len())
+            .max()
+            .unwrap_or(0);
+
+        for bucket in &self.0 {
+            let bar = match most {
+                0 => 0,
+                _ => (bucket.count * BAR_WIDTH).div_ceil(most),
+            };
+            writeln!(
+                f,
+                "[{:>width$}, {:>width$}) {:<BAR_WIDTH$} {}",
+                bucket.start,
+                bucket.end,
+                "█".repeat(bar),
+                bucket.count
+            )?;
+        }
+        Ok(())
+    }
+}
+
+/// Summary of the statistics of the elves.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Report {
+    pub count: usize,
+    pub min: i64,
+    pub max: i64,
+    pub mean: f64,
+    pub median: f64,
+    pub std_dev: f64,
+    /// Pairs of the requested percentile and its value.
+    pub percentiles: Vec<(f64, f64)>,
+    pub histogram: Histogram,
+    /// Indices of the elves whose total is an outlier.
+    pub outliers: Vec<usize>,
+}
+
+impl fmt::Display for Report {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "elves:    {}", self.count)?;
+        writeln!(f, "min:      {}", self.min)?;
+        writeln!(f, "max:      {}", self.max)?;
+        writeln!(f, "mean:     {:.1}", self.mean)?;
+        writeln!(f, "median:   {:.1}", self.median)?;
+        writeln!(f, "std dev:  {:.1}", self.std_dev)?;
+        for (percent, value) in &self.percentiles {
+            writeln!(f, "p{percent:<8}{value:.1}")?;
+        }
+
+        // elves are numbered from 1 for humans
+        let outliers = self
+            .outliers
+            .iter()
+            .map(|index| (index + 1).to_string())
+            .collect::<Vec<_>>();
+        match outliers.is_empty() {
+            true => writeln!(f, "outliers: none")?,
+            false => writeln!(f, "outliers: elves {}", outliers.join(", "))?,
+        }
+
+        writeln!(f)?;
+        write!(f, "{}", self.histogram)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn elves(totals: &[i64]) -> Vec<Elf> {
+        totals
+            .iter()
+            .enumerate()
+            .map(|(index, &total)| Elf {
+                index,
+                items: vec![total],
+                total,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_statistics() {
+        let statistics = Statistics::new(&elves(&[6000, 4000, 11000, 24000, 10000])).unwrap();
+        assert_eq!(statistics.count(), 5);
+        assert_eq!(statistics.min(), 4000);
+        assert_eq!(statistics.max(), 24000);
+        assert_eq!(statistics.mean(), 11000.0);
+        assert_eq!(statistics.median(), 10000.0);
+        assert_eq!(statistics.std_dev(), 6985.699678629192);
+        assert_eq!(statistics.percentile(0.0), 4000.0);
+        assert_eq!(statistics.percentile(90.0), 18800.0);
+        assert!(Statistics::new(&[]).is_none());
+    }
+
+    #[test]
+    fn test_histogram() {
+        let statistics = Statistics::new(&elves(&[1, 2, 3, 9, 10])).unwrap();
+        assert_eq!(
+            statistics.histogram(2),
+            Histogram(vec![
+                Bucket {
+                    start: 1,
+                    end: 6,
+                    count: 3
+                },
+                Bucket {
+                    start: 6,
+                    end: 11,
+                    count: 2
+                }
+            ])
+        );
+    }
+
+    #[test]
+    fn test_histogram_full_range() {
+        let statistics = Statistics::new(&elves(&[i64::MAX, 0])).unwrap();
+        let end = i128::from(i64::MAX) + 1;
+        assert_eq!(
+            statistics.histogram(1),
+            Histogram(vec![Bucket {
+                start: 0,
+                end,
+                count: 2
+            }])
+        );
+        let buckets = statistics.histogram(2).0;
+        assert_eq!(buckets[1].end, end);
+        assert_eq!(
+            buckets
+                .iter()
+                .map(|bucket| bucket.count)
+                .collect::<Vec<_>>(),
+            vec![1, 1]
+        );
+        assert_eq!(
+            serde_json::to_string(&buckets[1]).unwrap(),
+            format!("{{\"start\":{},\"end\":{end},\"count\":1}}", end / 2)
+        );
+    }
+
+    #[test]
+    fn test_outliers() {
+        let statistics = Statistics::new(&elves(&[10, 12, 11, 13, 100, 12])).unwrap();
+        assert_eq!(statistics.outliers(), vec![4]);
+    }
+}
@@ -0,0 +1,253 @@
+use std::{error::Error, fmt, io, io::BufRead};
+
+use super::{
+    checked::{self, CalorieError, CalorieErrorKind, Calories},
+    Leaders,
+};
+use crate::top_k::TopK;
+
+/// Error while reading calories from a stream.
+#[derive(Debug)]
+pub enum StreamError {
+    Io(io::Error),
+    Calories(CalorieError),
+}
+
+impl fmt::Display for StreamError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            StreamError::Io(error) => write!(f, "could not read input: {error}"),
+            StreamError::Calories(error) => write!(f, "{error}"),
+        }
+    }
+}
+
+impl Error for StreamError {}
+
+impl From<io::Error> for StreamError {
+    fn from(error: io::Error) -> Self {
+        StreamError::Io(error)
+    }
+}
+
+/// Aggregates the calories of elves one item at a time, only remembering the `k` elves carrying
+/// the most.
+///
+/// Memory usage is independent of the number of elves, so arbitrarily long logs can be processed.
+/// The totals of the elves are accumulated in `T`, see `CalorieAggregator::wide`.
+#[derive(Debug, Clone)]
+pub struct CalorieAggregator<T = i64> {
+    top: TopK<T>,
+    current: T,
+    finished: usize,
+    lines: usize,
+}
+
+impl CalorieAggregator {
+    /// Create an aggregator keeping track of the `k` elves carrying the most.
+    pub fn new(k: usize) -> CalorieAggregator {
+        CalorieAggregator::with_k(k)
+    }
+
+    /// Get the `k` elves carrying the most from a complete puzzle input.
+    pub fn from_reader(reader: impl BufRead, k: usize) -> Result<Leaders, StreamError> {
+        CalorieAggregator::new(k).read_to_end(reader)
+    }
+}
+
+impl CalorieAggregator<i128> {
+    /// Create an aggregator like `new`, but accumulate the totals in an `i128`.
+    pub fn wide(k: usize) -> CalorieAggregator<i128> {
+        CalorieAggregator::with_k(k)
+    }
+
+    /// Get the `k` elves carrying the most from a complete puzzle input, accumulating the totals
+    /// in an `i128`.
+    pub fn from_reader_wide(reader: impl BufRead, k: usize) -> Result<Leaders, StreamError> {
+        CalorieAggregator::wide(k).read_to_end(reader)
+    }
+}
+
+impl<T: Calories> CalorieAggregator<T> {
+    fn with_k(k: usize) -> CalorieAggregator<T> {
+        CalorieAggregator {
+            top: TopK::new(k),
+            current: T::ZERO,
+            finished: 0,
+            lines: 0,
+        }
+    }
+
+    /// Add an item to the elf currently being counted.
+    ///
+    /// Negative calories and totals which do not fit into `T` are rejected, leaving the current
+    /// elf unchanged.
+    pub fn add_item(&mut self, calories: i64) -> Result<(), CalorieErrorKind> {
+        if calories < 0 {
+            return Err(CalorieErrorKind::Negative(calories.to_string()));
+        }
+        self.current = self
+            .current
+            .checked_add(T::from(calories))
+            .ok_or(CalorieErrorKind::Overflow)?;
+        Ok(())
+    }
+
+    /// Finish the elf currently being counted and start with the next one.
+    pub fn finish_elf(&mut self) {
+        self.top.push(self.finished, self.current);
+        self.finished += 1;
+        self.current = T::ZERO;
+    }
+
+    /// Number of elves which have been finished.
+    pub fn elves(&self) -> usize {
+        self.finished
+    }
+
+    /// Calories of the elf currently being counted.
+    pub fn current(&self) -> T {
+        self.current
+    }
+
+    /// Get the finished elves carrying the most, the elf currently being counted is not included.
+    pub fn leaders(&self) -> Leaders {
+        Leaders::from_ranked(&self.top.to_sorted_vec())
+    }
+
+    /// Add all items of the given reader, in the format of the puzzle input.
+    ///
+    /// Every empty line finishes an elf, but the last elf stays open since the stream might be
+    /// continued. Use `finish_elf` once the input is complete.
+    pub fn consume(&mut self, mut reader: impl BufRead) -> Result<(), StreamError> {
+        let mut line = String::new();
+        loop {
+            line.clear();
+            if reader.read_line(&mut line)? == 0 {
+                return Ok(());
+            }
+            self.lines += 1;
+
+            let item = line.trim();
+            if item.is_empty() {
+                self.finish_elf();
+            } else {
+                checked::parse_item(item)
+                    .and_then(|calories| self.add_item(calories))
+                    .map_err(|kind| {
+                        StreamError::Calories(CalorieError {
+                            line: self.lines,
+                            kind,
+                        })
+                    })?;
+            }
+        }
+    }
+
+    fn read_to_end(mut self, reader: impl BufRead) -> Result<Leaders, StreamError> {
+        self.consume(reader)?;
+        self.finish_elf();
+        Ok(self.leaders())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const INPUT: &str = "1000
+2000
+3000
+
+4000
+
+5000
+6000
+
+7000
+8000
+9000
+
+10000
+";
+
+    #[test]
+    fn test_from_reader() {
+        assert_eq!(
+            CalorieAggregator::from_reader(INPUT.as_bytes(), 3).unwrap(),
+            Leaders {
+                total: 45000,
+                elves: vec![3, 2, 4]
+            }
+        );
+    }
+
+    #[test]
+    fn test_incremental_updates() {
+        let mut aggregator = CalorieAggregator::new(2);
+        aggregator.add_item(100).unwrap();
+        aggregator.add_item(200).unwrap();
+        aggregator.finish_elf();
+        aggregator.add_item(500).unwrap();
+        assert_eq!(aggregator.leaders().elves, vec![0]);
+        assert_eq!(aggregator.current(), 500);
+
+        aggregator.finish_elf();
+        aggregator.add_item(50).unwrap();
+        aggregator.finish_elf();
+        assert_eq!(aggregator.elves(), 3);
+        assert_eq!(
+            aggregator.leaders(),
+            Leaders {
+                total: 800,
+                elves: vec![1, 0]
+            }
+        );
+    }
+
+    #[test]
+    fn test_add_item_checked() {
+        let mut aggregator = CalorieAggregator::new(1);
+        aggregator.add_item(i64::MAX).unwrap();
+        assert_eq!(aggregator.add_item(1), Err(CalorieErrorKind::Overflow));
+        assert_eq!(
+            aggregator.add_item(-1),
+            Err(CalorieErrorKind::Negative("-1".to_string()))
+        );
+        assert_eq!(aggregator.current(), i64::MAX);
+    }
+
+    #[test]
+    fn test_wide_totals() {
+        let inp = "9223372036854775807\n1\n\n5\n";
+        assert!(CalorieAggregator::from_reader(inp.as_bytes(), 1).is_err());
+        assert_eq!(
+            CalorieAggregator::from_reader_wide(inp.as_bytes(), 2).unwrap(),
+            Leaders {
+                total: i128::from(i64::MAX) + 6,
+                elves: vec![0, 1]
+            }
+        );
+
+        let mut aggregator = CalorieAggregator::wide(1);
+        aggregator.add_item(i64::MAX).unwrap();
+        aggregator.add_item(i64::MAX).unwrap();
+        assert_eq!(aggregator.current(), 2 * i128::from(i64::MAX));
+    }
+
+    #[test]
+    fn test_consume_invalid_line() {
+        let mut aggregator = CalorieAggregator::new(1);
+        let error = aggregator.consume("1\n\nabc\n".as_bytes()).unwrap_err();
+        assert_eq!(error.to_string(), "line 3: 'abc' is no number");
+
+        let mut aggregator = CalorieAggregator::new(1);
+        assert!(matches!(
+            aggregator.consume("1\n-2\n".as_bytes()),
+            Err(StreamError::Calories(CalorieError {
+                line: 2,
+                kind: CalorieErrorKind::Negative(_)
+            }))
+        ));
+    }
+}
@@ -1,83 +1,20 @@
 use aoc_runner_derive::{aoc, aoc_generator};
 
-/// Enum representing a hand in rock-paper-scissors
-#[derive(Debug, Clone, Copy, Eq, PartialEq)]
-enum Hand {
-    Rock = 1,
-    Paper = 2,
-    Scissor = 3,
-}
-
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
-enum Outcome {
-    Loose = 0,
-    Draw = 3,
-    Win = 6,
-}
-
-impl Hand {
-    /// Parse the input into a valid hand.
-    fn from_code(code: &str) -> Hand {
-        match code {
-            "A" | "X" => Self::Rock,
-            "B" | "Y" => Self::Paper,
-            "C" | "Z" => Self::Scissor,
-            _ => unreachable!(),
-        }
-    }
-
-    /// Calculate the outcome of a game of rock-paper-scissors.
-    fn beats(&self, other: Hand) -> Outcome {
-        match (*self, other) {
-            (Hand::Rock, Hand::Paper)
-            | (Hand::Paper, Hand::Scissor)
-            | (Hand::Scissor, Hand::Rock) => Outcome::Loose,
-            (Hand::Paper, Hand::Rock)
-            | (Hand::Scissor, Hand::Paper)
-            | (Hand::Rock, Hand::Scissor) => Outcome::Win,
-            _ => Outcome::Draw,
-        }
-    }
-
-    /// Get the hand that would result in the specified outcome if played against this hand.
-    fn with_coutcome_against_this(&self, outcome: Outcome) -> Hand {
-        match (*self, outcome) {
-            (Hand::Rock, Outcome::Draw)
-            | (Hand::Scissor, Outcome::Win)
-            | (Hand::Paper, Outcome::Loose) => Self::Rock,
-            (Hand::Paper, Outcome::Draw)
-            | (Hand::Rock, Outcome::Win)
-            | (Hand::Scissor, Outcome::Loose) => Self::Paper,
-            (Hand::Scissor, Outcome::Draw)
-            | (Hand::Paper, Outcome::Win)
-            | (Hand::Rock, Outcome::Loose) => Self::Scissor,
-        }
-    }
+use crate::solver::{Part, Run};
 
-    fn val(self) -> i64 {
-        self as i64
-    }
-}
+pub mod breakdown;
+pub mod game;
+pub mod key;
+pub mod lut;
+pub mod play;
+pub mod scoring;
+pub mod tournament;
 
-impl Outcome {
-    /// Parse the input into a valid outcome.
-    fn from_code(code: &str) -> Self {
-        match code {
-            "X" => Self::Loose,
-            "Y" => Self::Draw,
-            "Z" => Self::Win,
-            _ => unreachable!(),
-        }
-    }
+use game::{CyclicGame, Hand, Outcome};
+use scoring::ScoringRules;
 
-    fn val(self) -> i64 {
-        self as i64
-    }
-}
-
-/// Convert the sample input into a valid vector of hands.
-#[aoc_generator(day2, part1)]
-fn generator_day02_part1(inp: &str) -> Vec<(Hand, Hand)> {
+/// Parse a strategy guide of the given game into the hands of the enemy and our hands.
+pub fn parse_hands(game: &CyclicGame, inp: &str) -> Vec<(Hand, Hand)> {
     let mut hands = vec![];
 
     for line in inp.lines() {
@@ -86,15 +23,17 @@ fn generator_day02_part1(inp: &str) -> Vec<(Hand, Hand)> {
         // But since I do not care enough for safety in AOC...LUL
         let enemy = coded_hands.next().unwrap();
         let we = coded_hands.next().unwrap();
-        hands.push((Hand::from_code(enemy), Hand::from_code(we)));
+        hands.push((
+            game.opponent_code(enemy).unwrap(),
+            game.own_code(we).unwrap(),
+        ));
     }
 
     hands
 }
 
-/// Conver the sample input into a vector containing the hand of the enemy and the desired outcome.
-#[aoc_generator(day2, part2)]
-fn generator_day02_part2(inp: &str) -> Vec<(Hand, Outcome)> {
+/// Parse a strategy guide of the given game into the hands of the enemy and the desired outcomes.
+pub fn parse_outcomes(game: &CyclicGame, inp: &str) -> Vec<(Hand, Outcome)> {
     let mut rounds = vec![];
 
     for line in inp.lines() {
@@ -103,32 +42,63 @@ fn generator_day02_part2(inp: &str) -> Vec<(Hand, Outcome)> {
         // But since I do not care enough for safety in AOC...LUL
         let enemy = coded_rounds.next().unwrap();
         let outcome = coded_rounds.next().unwrap();
-        rounds.push((Hand::from_code(enemy), Outcome::from_code(outcome)));
+        rounds.push((
+            game.opponent_code(enemy).unwrap(),
+            Outcome::from_code(outcome).unwrap(),
+        ));
     }
 
     rounds
 }
 
+/// Total score of playing the given hands.
+pub fn score_hands(game: &CyclicGame, rules: &ScoringRules, hands: &[(Hand, Hand)]) -> i64 {
+    hands.iter().fold(0, |score, &(enemy, we)| {
+        score + rules.score(game, enemy, we)
+    })
+}
+
+/// Total score of playing for the given outcomes.
+pub fn score_outcomes(game: &CyclicGame, rules: &ScoringRules, rounds: &[(Hand, Outcome)]) -> i64 {
+    rounds
+        .iter()
+        .map(|&(enemy, outcome)| (enemy, game.with_outcome_against(enemy, outcome)))
+        .fold(0, |score, (enemy, we)| score + rules.score(game, enemy, we))
+}
+
+/// Convert the sample input into a valid vector of hands.
+#[aoc_generator(day2, part1)]
+fn generator_day02_part1(inp: &str) -> Vec<(Hand, Hand)> {
+    parse_hands(&CyclicGame::rock_paper_scissors(), inp)
+}
+
+/// Conver the sample input into a vector containing the hand of the enemy and the desired outcome.
+#[aoc_generator(day2, part2)]
+fn generator_day02_part2(inp: &str) -> Vec<(Hand, Outcome)> {
+    parse_outcomes(&CyclicGame::rock_paper_scissors(), inp)
+}
+
 #[aoc(day2, part1)]
 fn day02_part1(hands: &[(Hand, Hand)]) -> i64 {
-    hands.iter().fold(0, |score, hands| {
-        let (enemy, we) = hands;
-        score + we.val() + we.beats(*enemy).val()
-    })
+    let game = CyclicGame::rock_paper_scissors();
+    score_hands(&game, &ScoringRules::standard(&game), hands)
 }
 
 #[aoc(day2, part2)]
 fn day02_part2(rounds: &[(Hand, Outcome)]) -> i64 {
-    rounds
-        .iter()
-        .map(|round| {
-            let (enemy, outcome) = round;
-            (enemy, enemy.with_coutcome_against_this(*outcome))
-        })
-        .fold(0, |score, hands| {
-            let (enemy, we) = hands;
-            score + we.val() + we.beats(*enemy).val()
-        })
+    let game = CyclicGame::rock_paper_scissors();
+    score_outcomes(&game, &ScoringRules::standard(&game), rounds)
+}
+
+/// Run the given part of this day for the provided input.
+pub(crate) fn run(part: Part, input: &str) -> Run {
+    match part {
+        Part::One => Run::measure(|| generator_day02_part1(input), |hands| day02_part1(hands)),
+        Part::Two => Run::measure(
+            || generator_day02_part2(input),
+            |rounds| day02_part2(rounds),
+        ),
+    }
 }
 
 #[cfg(test)]
@@ -141,59 +111,35 @@ C Z";
 
     #[test]
     fn test_hand_beats() {
-        assert_eq!(Hand::Rock.beats(Hand::Scissor), Outcome::Win);
-        assert_eq!(Hand::Paper.beats(Hand::Rock), Outcome::Win);
-        assert_eq!(Hand::Scissor.beats(Hand::Paper), Outcome::Win);
-
-        assert_eq!(Hand::Scissor.beats(Hand::Rock), Outcome::Loose);
-        assert_eq!(Hand::Rock.beats(Hand::Paper), Outcome::Loose);
-        assert_eq!(Hand::Paper.beats(Hand::Scissor), Outcome::Loose);
-
-        assert_eq!(Hand::Rock.beats(Hand::Rock), Outcome::Draw);
-        assert_eq!(Hand::Paper.beats(Hand::Paper), Outcome::Draw);
-        assert_eq!(Hand::Scissor.beats(Hand::Scissor), Outcome::Draw);
+        let game = CyclicGame::rock_paper_scissors();
+        assert_eq!(game.beats(Hand::ROCK, Hand::SCISSORS), Outcome::Win);
+        assert_eq!(game.beats(Hand::PAPER, Hand::ROCK), Outcome::Win);
+        assert_eq!(game.beats(Hand::SCISSORS, Hand::PAPER), Outcome::Win);
+
+        assert_eq!(game.beats(Hand::SCISSORS, Hand::ROCK), Outcome::Loose);
+        assert_eq!(game.beats(Hand::ROCK, Hand::PAPER), Outcome::Loose);
+        assert_eq!(game.beats(Hand::PAPER, Hand::SCISSORS), Outcome::Loose);
+
+        assert_eq!(game.beats(Hand::ROCK, Hand::ROCK), Outcome::Draw);
+        assert_eq!(game.beats(Hand::PAPER, Hand::PAPER), Outcome::Draw);
+        assert_eq!(game.beats(Hand::SCISSORS, Hand::SCISSORS), Outcome::Draw);
     }
 
     #[test]
     fn test_hand_with_outcome_against_this() {
-        assert_eq!(
-            Hand::Rock.with_coutcome_against_this(Outcome::Draw),
-            Hand::Rock
-        );
-        assert_eq!(
-            Hand::Paper.with_coutcome_against_this(Outcome::Draw),
-            Hand::Paper
-        );
-        assert_eq!(
-            Hand::Scissor.with_coutcome_against_this(Outcome::Draw),
-            Hand::Scissor
-        );
-
-        assert_eq!(
-            Hand::Rock.with_coutcome_against_this(Outcome::Win),
-            Hand::Paper
-        );
-        assert_eq!(
-            Hand::Paper.with_coutcome_against_this(Outcome::Win),
-            Hand::Scissor
-        );
-        assert_eq!(
-            Hand::Scissor.with_coutcome_against_this(Outcome::Win),
-            Hand::Rock
-        );
-
-        assert_eq!(
-            Hand::Rock.with_coutcome_against_this(Outcome::Loose),
-            Hand::Scissor
-        );
-        assert_eq!(
-            Hand::Scissor.with_coutcome_against_this(Outcome::Loose),
-            Hand::Paper
-        );
-        assert_eq!(
-            Hand::Paper.with_coutcome_against_this(Outcome::Loose),
-            Hand::Rock
-        );
+        let game = CyclicGame::rock_paper_scissors();
+        let against = |hand, outcome| game.with_outcome_against(hand, outcome);
+        assert_eq!(against(Hand::ROCK, Outcome::Draw), Hand::ROCK);
+        assert_eq!(against(Hand::PAPER, Outcome::Draw), Hand::PAPER);
+        assert_eq!(against(Hand::SCISSORS, Outcome::Draw), Hand::SCISSORS);
+
+        assert_eq!(against(Hand::ROCK, Outcome::Win), Hand::PAPER);
+        assert_eq!(against(Hand::PAPER, Outcome::Win), Hand::SCISSORS);
+        assert_eq!(against(Hand::SCISSORS, Outcome::Win), Hand::ROCK);
+
+        assert_eq!(against(Hand::ROCK, Outcome::Loose), Hand::SCISSORS);
+        assert_eq!(against(Hand::SCISSORS, Outcome::Loose), Hand::PAPER);
+        assert_eq!(against(Hand::PAPER, Outcome::Loose), Hand::ROCK);
     }
 
     #[test]
@@ -202,9 +148,9 @@ C Z";
         assert_eq!(
             hands,
             vec![
-                (Hand::Rock, Hand::Paper),
-                (Hand::Paper, Hand::Rock),
-                (Hand::Scissor, Hand::Scissor)
+                (Hand::ROCK, Hand::PAPER),
+                (Hand::PAPER, Hand::ROCK),
+                (Hand::SCISSORS, Hand::SCISSORS)
             ]
         );
     }
@@ -215,9 +161,9 @@ C Z";
         assert_eq!(
             rounds,
             vec![
-                (Hand::Rock, Outcome::Draw),
-                (Hand::Paper, Outcome::Loose),
-                (Hand::Scissor, Outcome::Win)
+                (Hand::ROCK, Outcome::Draw),
+                (Hand::PAPER, Outcome::Loose),
+                (Hand::SCISSORS, Outcome::Win)
             ]
         );
     }
@@ -233,4 +179,20 @@ C Z";
         let rounds = generator_day02_part2(INPUT);
         assert_eq!(day02_part2(&rounds), 12);
     }
+
+    #[test]
+    fn test_lizard_spock_guide() {
+        let game = CyclicGame::rock_paper_scissors_lizard_spock();
+        let rules = ScoringRules::standard(&game);
+        let hands = parse_hands(&game, "A X\nD V\nE Z");
+        // paper beats rock, rock crushes lizard, scissors draw
+        assert_eq!(
+            score_hands(&game, &rules, &hands),
+            (3 + 6) + (1 + 6) + (5 + 3)
+        );
+
+        let rounds = parse_outcomes(&game, "B Z\nC X");
+        // paper disproves spock, spock is disproved by paper
+        assert_eq!(score_outcomes(&game, &rules, &rounds), (3 + 6) + 2);
+    }
 }
@@ -0,0 +1,229 @@
+use std::fmt;
+
+use super::{
+    game::{CyclicGame, Hand, Outcome},
+    scoring::ScoringRules,
+};
+
+/// A single round of a strategy guide.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Round {
+    pub enemy: Hand,
+    pub we: Hand,
+    pub outcome: Outcome,
+    pub points: i64,
+    /// Score after this round.
+    pub running: i64,
+}
+
+/// Number of rounds and points with the same outcome or hand.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct Tally {
+    pub rounds: usize,
+    pub points: i64,
+}
+
+impl Tally {
+    fn add(&mut self, points: i64) {
+        self.rounds += 1;
+        self.points += points;
+    }
+}
+
+/// How the score of a strategy guide was obtained.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Breakdown {
+    game: CyclicGame,
+    pub rounds: Vec<Round>,
+    pub wins: Tally,
+    pub draws: Tally,
+    pub losses: Tally,
+    /// Tallies of the hands we played, indexed by the hand.
+    pub hands: Vec<Tally>,
+    pub longest_win_streak: usize,
+}
+
+impl Breakdown {
+    /// Break down playing the given hands.
+    pub fn new(game: &CyclicGame, rules: &ScoringRules, hands: &[(Hand, Hand)]) -> Breakdown {
+        let mut breakdown = Breakdown {
+            game: game.clone(),
+            rounds: Vec::with_capacity(hands.len()),
+            wins: Tally::default(),
+            draws: Tally::default(),
+            losses: Tally::default(),
+            hands: vec![Tally::default(); game.len()],
+            longest_win_streak: 0,
+        };
+
+        let mut running = 0;
+        let mut streak = 0;
+        for &(enemy, we) in hands {
+            let outcome = game.beats(we, enemy);
+            let points = rules.score(game, enemy, we);
+            running += points;
+            breakdown.rounds.push(Round {
+                enemy,
+                we,
+                outcome,
+                points,
+                running,
+            });
+
+            match outcome {
+                Outcome::Win => breakdown.wins.add(points),
+                Outcome::Draw => breakdown.draws.add(points),
+                Outcome::Loose => breakdown.losses.add(points),
+            }
+            breakdown.hands[we.index()].add(points);
+
+            streak = if outcome == Outcome::Win {
+                streak + 1
+            } else {
+                0
+            };
+            breakdown.longest_win_streak = breakdown.longest_win_streak.max(streak);
+        }
+        breakdown
+    }
+
+    /// Break down playing for the given outcomes.
+    pub fn from_outcomes(
+        game: &CyclicGame,
+        rules: &ScoringRules,
+        rounds: &[(Hand, Outcome)],
+    ) -> Breakdown {
+        let hands = rounds
+            .iter()
+            .map(|&(enemy, outcome)| (enemy, game.with_outcome_against(enemy, outcome)))
+            .collect::<Vec<_>>();
+        Breakdown::new(game, rules, &hands)
+    }
+
+    pub fn total(&self) -> i64 {
+        self.rounds.last().map_or(0, |round| round.running)
+    }
+}
+
+impl fmt::Display for Breakdown {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let width = self
+            .game
+            .hands()
+            .map(|hand| self.game.name(hand).len())
+            .max()
+            .unwrap_or(0)
+            .max("enemy".len());
+
+        writeln!(
+            f,
+            "{:>6}  {:<width$}  {:<width$}  {:<7} {:>6} {:>8}",
+            "round", "enemy", "we", "outcome", "points", "score"
+        )?;
+        // rounds are numbered from 1 for humans
+        for (i, round) in self.rounds.iter().enumerate() {
+            writeln!(
+                f,
+                "{:>6}  {:<width$}  {:<width$}  {:<7} {:>6} {:>8}",
+                i + 1,
+                self.game.name(round.enemy),
+                self.game.name(round.we),
+                round.outcome,
+                round.points,
+                round.running
+            )?;
+        }
+
+        writeln!(f)?;
+        for (name, tally) in [
+            ("wins", self.wins),
+            ("draws", self.draws),
+            ("losses", self.losses),
+        ] {
+            writeln!(
+                f,
+                "{name:<width$}  {:>6} rounds {:>8} points",
+                tally.rounds, tally.points
+            )?;
+        }
+        for (hand, tally) in self.game.hands().zip(&self.hands) {
+            writeln!(
+                f,
+                "{:<width$}  {:>6} rounds {:>8} points",
+                self.game.name(hand),
+                tally.rounds,
+                tally.points
+            )?;
+        }
+        writeln!(f, "longest win streak: {}", self.longest_win_streak)?;
+        writeln!(f, "total: {}", self.total())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_breakdown() {
+        let game = CyclicGame::rock_paper_scissors();
+        let rules = ScoringRules::standard(&game);
+        let breakdown = Breakdown::new(
+            &game,
+            &rules,
+            &[
+                (Hand::ROCK, Hand::PAPER),
+                (Hand::PAPER, Hand::SCISSORS),
+                (Hand::PAPER, Hand::ROCK),
+                (Hand::SCISSORS, Hand::ROCK),
+                (Hand::SCISSORS, Hand::SCISSORS),
+            ],
+        );
+
+        assert_eq!(
+            breakdown
+                .rounds
+                .iter()
+                .map(|round| round.running)
+                .collect::<Vec<_>>(),
+            vec![8, 17, 18, 25, 31]
+        );
+        assert_eq!(breakdown.total(), 31);
+        assert_eq!(
+            breakdown.wins,
+            Tally {
+                rounds: 3,
+                points: 24
+            }
+        );
+        assert_eq!(breakdown.draws.points, 6);
+        assert_eq!(breakdown.losses.rounds, 1);
+        assert_eq!(
+            breakdown.hands[Hand::ROCK.index()],
+            Tally {
+                rounds: 2,
+                points: 8
+            }
+        );
+        assert_eq!(breakdown.longest_win_streak, 2);
+    }
+
+    #[test]
+    fn test_breakdown_from_outcomes() {
+        let game = CyclicGame::rock_paper_scissors();
+        let breakdown = Breakdown::from_outcomes(
+            &game,
+            &ScoringRules::standard(&game),
+            &[
+                (Hand::ROCK, Outcome::Draw),
+                (Hand::PAPER, Outcome::Loose),
+                (Hand::SCISSORS, Outcome::Win),
+            ],
+        );
+        assert_eq!(breakdown.total(), 12);
+
+        let table = breakdown.to_string();
+        assert!(table.contains("     2  Paper     Rock      loss         1        5\n"));
+        assert!(table.ends_with("longest win streak: 1\ntotal: 12\n"));
+    }
+}
@@ -0,0 +1,241 @@
+use std::{fmt, str::FromStr};
+
+/// Smallest number of hands, so every game has the hands of rock-paper-scissors.
+pub const MIN_HANDS: usize = 3;
+
+/// Largest number of hands, so the codes of both columns (`A`.. and ..`Z`) do not overlap.
+pub const MAX_HANDS: usize = 13;
+
+/// A hand, identified by its position in the cycle of a game.
+///
+/// Hands are only created by a `CyclicGame`, so they are valid for the game they come from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Hand(usize);
+
+impl Hand {
+    /// Hands of `CyclicGame::rock_paper_scissors`, which are the first hands of every game.
+    pub const ROCK: Hand = Hand(0);
+    pub const PAPER: Hand = Hand(1);
+    pub const SCISSORS: Hand = Hand(2);
+
+    /// Position of the hand in the cycle of its game, starting at 0.
+    pub fn index(self) -> usize {
+        self.0
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Outcome {
+    Loose = 0,
+    Draw = 3,
+    Win = 6,
+}
+
+impl Outcome {
+    /// Parse the code of a desired outcome (`X`, `Y` or `Z`).
+    pub fn from_code(code: &str) -> Option<Outcome> {
+        match code {
+            "X" => Some(Outcome::Loose),
+            "Y" => Some(Outcome::Draw),
+            "Z" => Some(Outcome::Win),
+            _ => None,
+        }
+    }
+
+    pub fn val(self) -> i64 {
+        self as i64
+    }
+}
+
+impl fmt::Display for Outcome {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.pad(match self {
+            Outcome::Loose => "loss",
+            Outcome::Draw => "draw",
+            Outcome::Win => "win",
+        })
+    }
+}
+
+/// A game with an odd number of hands arranged in a cycle, in which every hand beats the
+/// (n - 1) / 2 hands before it and looses against the ones after it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CyclicGame {
+    names: Vec<String>,
+}
+
+impl CyclicGame {
+    /// Create a game from the names of its hands in cycle order.
+    ///
+    /// Returns `None` unless the number of hands is odd and between `MIN_HANDS` and `MAX_HANDS`.
+    pub fn new<S: Into<String>>(names: impl IntoIterator<Item = S>) -> Option<CyclicGame> {
+        let names = names.into_iter().map(Into::into).collect::<Vec<_>>();
+        if names.len() % 2 == 0 || !(MIN_HANDS..=MAX_HANDS).contains(&names.len()) {
+            return None;
+        }
+        Some(CyclicGame { names })
+    }
+
+    pub fn rock_paper_scissors() -> CyclicGame {
+        CyclicGame::new(["Rock", "Paper", "Scissors"]).expect("the preset is valid")
+    }
+
+    pub fn rock_paper_scissors_lizard_spock() -> CyclicGame {
+        CyclicGame::new(["Rock", "Spock", "Paper", "Lizard", "Scissors"])
+            .expect("the preset is valid")
+    }
+
+    /// Number of hands.
+    pub fn len(&self) -> usize {
+        self.names.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.names.is_empty()
+    }
+
+    pub fn hands(&self) -> impl Iterator<Item = Hand> {
+        (0..self.len()).map(Hand)
+    }
+
+    /// Get the hand at the given position in the cycle, if the game has that many hands.
+    pub fn hand_at(&self, index: usize) -> Option<Hand> {
+        (index < self.len()).then_some(Hand(index))
+    }
+
+    pub fn name(&self, hand: Hand) -> &str {
+        &self.names[hand.0]
+    }
+
+    /// Find a hand by its name, ignoring case.
+    pub fn hand(&self, name: &str) -> Option<Hand> {
+        self.names
+            .iter()
+            .position(|candidate| candidate.eq_ignore_ascii_case(name))
+            .map(Hand)
+    }
+
+    /// Parse the code of the opponent's hand, the first `len` letters of the alphabet.
+    pub fn opponent_code(&self, code: &str) -> Option<Hand> {
+        self.letter(code, b'A')
+    }
+
+    /// Parse the code of our own hand, the last `len` letters of the alphabet.
+    pub fn own_code(&self, code: &str) -> Option<Hand> {
+        self.letter(code, b'Z' + 1 - self.len() as u8)
+    }
+
+    fn letter(&self, code: &str, first: u8) -> Option<Hand> {
+        match code.as_bytes() {
+            &[letter] if (first..first + self.len() as u8).contains(&letter) => {
+                Some(Hand((letter - first) as usize))
+            }
+            _ => None,
+        }
+    }
+
+    /// Calculate the outcome of playing `hand` against `other`.
+    pub fn beats(&self, hand: Hand, other: Hand) -> Outcome {
+        let distance = (hand.0 + self.len() - other.0) % self.len();
+        match distance {
+            0 => Outcome::Draw,
+            d if d <= self.len() / 2 => Outcome::Win,
+            _ => Outcome::Loose,
+        }
+    }
+
+    /// Get a hand that results in the specified outcome if played against `other`.
+    pub fn with_outcome_against(&self, other: Hand, outcome: Outcome) -> Hand {
+        let offset = match outcome {
+            Outcome::Draw => 0,
+            Outcome::Win => 1,
+            Outcome::Loose => self.len() - 1,
+        };
+        Hand((other.0 + offset) % self.len())
+    }
+}
+
+impl fmt::Display for CyclicGame {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.names.join("-"))
+    }
+}
+
+impl FromStr for CyclicGame {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "rps" => Ok(CyclicGame::rock_paper_scissors()),
+            "rpsls" => Ok(CyclicGame::rock_paper_scissors_lizard_spock()),
+            _ => Err(format!("unknown game '{s}' (expected rps or rpsls)")),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_new() {
+        assert!(CyclicGame::new(["Rock", "Paper"]).is_none());
+        assert!(CyclicGame::new(Vec::<String>::new()).is_none());
+        assert!(CyclicGame::new((0..15).map(|i| i.to_string())).is_none());
+        assert!(CyclicGame::new(["Coin"]).is_none());
+        assert_eq!(CyclicGame::new(["A", "B", "C"]).unwrap().len(), 3);
+    }
+
+    #[test]
+    fn test_lizard_spock() {
+        let game = CyclicGame::rock_paper_scissors_lizard_spock();
+        let beats = |a: &str, b: &str| game.beats(game.hand(a).unwrap(), game.hand(b).unwrap());
+
+        for (winner, looser) in [
+            ("scissors", "paper"),
+            ("paper", "rock"),
+            ("rock", "lizard"),
+            ("lizard", "spock"),
+            ("spock", "scissors"),
+            ("scissors", "lizard"),
+            ("lizard", "paper"),
+            ("paper", "spock"),
+            ("spock", "rock"),
+            ("rock", "scissors"),
+        ] {
+            assert_eq!(beats(winner, looser), Outcome::Win);
+            assert_eq!(beats(looser, winner), Outcome::Loose);
+        }
+        assert_eq!(beats("spock", "spock"), Outcome::Draw);
+    }
+
+    #[test]
+    fn test_with_outcome_against_any_game() {
+        let game = CyclicGame::rock_paper_scissors_lizard_spock();
+        for other in game.hands() {
+            for outcome in [Outcome::Loose, Outcome::Draw, Outcome::Win] {
+                let hand = game.with_outcome_against(other, outcome);
+                assert_eq!(game.beats(hand, other), outcome);
+            }
+        }
+    }
+
+    #[test]
+    fn test_codes() {
+        let game = CyclicGame::rock_paper_scissors_lizard_spock();
+        assert_eq!(game.opponent_code("E"), Some(Hand(4)));
+        assert_eq!(game.opponent_code("F"), None);
+        assert_eq!(game.own_code("V"), Some(Hand(0)));
+        assert_eq!(game.own_code("Z"), Some(Hand(4)));
+        assert_eq!(game.own_code("U"), None);
+        assert_eq!(game.to_string(), "Rock-Spock-Paper-Lizard-Scissors");
+    }
+
+    #[test]
+    fn test_hand_at() {
+        let game = CyclicGame::rock_paper_scissors();
+        assert_eq!(game.hand_at(2), Some(Hand::SCISSORS));
+        assert_eq!(game.hand_at(3), None);
+        assert_eq!(Hand::SCISSORS.index(), 2);
+    }
+}
@@ -0,0 +1,291 @@
+use std::{cmp::Reverse, collections::HashMap, error::Error, fmt};
+
+use super::{
+    game::{CyclicGame, Hand, Outcome},
+    scoring::ScoringRules,
+};
+
+/// Largest number of hands for which all keys are scored, there are `n!` of them.
+pub const MAX_KEY_HANDS: usize = 9;
+
+/// Meaning of the codes in the second column of a strategy guide.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Key {
+    /// The codes are our hands, indexed by the position of the code (`X` is 0 in
+    /// rock-paper-scissors).
+    Hands(Vec<Hand>),
+    /// The codes `X`, `Y` and `Z` are the desired outcomes.
+    Outcomes([Outcome; 3]),
+}
+
+/// Total score of a strategy guide when read with a key.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Interpretation {
+    pub key: Key,
+    pub score: i64,
+}
+
+/// Scores of a strategy guide under every possible key, the highest score first.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct KeyAnalysis {
+    pub interpretations: Vec<Interpretation>,
+}
+
+impl KeyAnalysis {
+    pub fn best(&self) -> Option<&Interpretation> {
+        self.interpretations.first()
+    }
+
+    pub fn worst(&self) -> Option<&Interpretation> {
+        self.interpretations.last()
+    }
+
+    /// Get all keys which reproduce the claimed score.
+    pub fn matching(&self, claimed: i64) -> Vec<&Interpretation> {
+        self.interpretations
+            .iter()
+            .filter(|interpretation| interpretation.score == claimed)
+            .collect()
+    }
+}
+
+/// Why a strategy guide can not be analyzed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum KeyError {
+    /// A line (counted from 1) with codes which are not part of the game.
+    InvalidLine(usize),
+    /// The game has more than `MAX_KEY_HANDS` hands.
+    TooManyHands(usize),
+}
+
+impl fmt::Display for KeyError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            KeyError::InvalidLine(line) => write!(f, "line {line} has no valid codes"),
+            KeyError::TooManyHands(hands) => write!(
+                f,
+                "a game with {hands} hands has too many keys (at most {MAX_KEY_HANDS} hands)"
+            ),
+        }
+    }
+}
+
+impl Error for KeyError {}
+
+/// Score the strategy guide under every mapping of the codes to our hands and, if it only uses
+/// `X`, `Y` and `Z`, under every mapping of them to outcomes.
+pub fn analyze(
+    game: &CyclicGame,
+    rules: &ScoringRules,
+    inp: &str,
+) -> Result<KeyAnalysis, KeyError> {
+    if game.len() > MAX_KEY_HANDS {
+        return Err(KeyError::TooManyHands(game.len()));
+    }
+
+    // every round of the same opponent hand and code scores the same, so count them once. Codes
+    // are counted by their position, i.e. as the hand they stand for with the default key.
+    let mut rounds = HashMap::<(Hand, Hand), i64>::new();
+    for (i, line) in inp.lines().enumerate() {
+        let mut codes = line.split_whitespace();
+        let round = codes
+            .next()
+            .and_then(|code| game.opponent_code(code))
+            .zip(codes.next().and_then(|code| game.own_code(code)))
+            .ok_or(KeyError::InvalidLine(i + 1))?;
+        *rounds.entry(round).or_default() += 1;
+    }
+
+    let mut interpretations = vec![];
+    for permutation in permutations(game.len()) {
+        let hands = permutation
+            .into_iter()
+            .map(|index| {
+                game.hand_at(index)
+                    .expect("permutations are of the hands of the game")
+            })
+            .collect::<Vec<_>>();
+        let score = rounds
+            .iter()
+            .map(|(&(enemy, code), &count)| count * rules.score(game, enemy, hands[code.index()]))
+            .sum();
+        interpretations.push(Interpretation {
+            key: Key::Hands(hands),
+            score,
+        });
+    }
+
+    // position of `X` among the codes of the game
+    let x = game.len() - 3;
+    if rounds.keys().all(|&(_, code)| code.index() >= x) {
+        let outcomes = [Outcome::Loose, Outcome::Draw, Outcome::Win];
+        for permutation in permutations(3) {
+            let key = [
+                outcomes[permutation[0]],
+                outcomes[permutation[1]],
+                outcomes[permutation[2]],
+            ];
+            let score = rounds
+                .iter()
+                .map(|(&(enemy, code), &count)| {
+                    let outcome = key[code.index() - x];
+                    count * rules.score(game, enemy, game.with_outcome_against(enemy, outcome))
+                })
+                .sum();
+            interpretations.push(Interpretation {
+                key: Key::Outcomes(key),
+                score,
+            });
+        }
+    }
+
+    interpretations.sort_by_key(|interpretation| Reverse(interpretation.score));
+    Ok(KeyAnalysis { interpretations })
+}
+
+/// Permutations of `0..n` in lexicographic order, generated one at a time.
+struct Permutations {
+    next: Option<Vec<usize>>,
+}
+
+fn permutations(n: usize) -> Permutations {
+    Permutations {
+        next: Some((0..n).collect()),
+    }
+}
+
+impl Iterator for Permutations {
+    type Item = Vec<usize>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let current = self.next.take()?;
+        // swap the last ascent with the smallest larger value behind it and reverse the
+        // descending rest, the last permutation has no ascent
+        if let Some(i) = current.windows(2).rposition(|pair| pair[0] < pair[1]) {
+            let mut next = current.clone();
+            let j = next
+                .iter()
+                .rposition(|&value| value > next[i])
+                .expect("the ascent has a larger value behind it");
+            next.swap(i, j);
+            next[i + 1..].reverse();
+            self.next = Some(next);
+        }
+        Some(current)
+    }
+}
+
+impl Key {
+    /// Describe the key, e.g. `X=Rock Y=Paper Z=Scissors`, using the names of the game.
+    pub fn describe(&self, game: &CyclicGame) -> String {
+        match self {
+            Key::Hands(hands) => {
+                let first = b'Z' + 1 - hands.len() as u8;
+                hands
+                    .iter()
+                    .enumerate()
+                    .map(|(i, &hand)| format!("{}={}", (first + i as u8) as char, game.name(hand)))
+                    .collect::<Vec<_>>()
+                    .join(" ")
+            }
+            Key::Outcomes(outcomes) => ["X", "Y", "Z"]
+                .iter()
+                .zip(outcomes)
+                .map(|(code, outcome)| format!("{code}={outcome}"))
+                .collect::<Vec<_>>()
+                .join(" "),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const INPUT: &str = "A Y
+B X
+C Z";
+
+    #[test]
+    fn test_permutations() {
+        assert_eq!(
+            permutations(3).collect::<Vec<_>>(),
+            vec![
+                vec![0, 1, 2],
+                vec![0, 2, 1],
+                vec![1, 0, 2],
+                vec![1, 2, 0],
+                vec![2, 0, 1],
+                vec![2, 1, 0]
+            ]
+        );
+        assert_eq!(permutations(5).count(), 120);
+        assert_eq!(
+            permutations(0).collect::<Vec<_>>(),
+            vec![Vec::<usize>::new()]
+        );
+        // only the permutations asked for are generated
+        assert_eq!(
+            permutations(13).nth(1),
+            Some(vec![0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 12, 11])
+        );
+    }
+
+    #[test]
+    fn test_analyze() {
+        let game = CyclicGame::rock_paper_scissors();
+        let analysis = analyze(&game, &ScoringRules::standard(&game), INPUT).unwrap();
+        assert_eq!(analysis.interpretations.len(), 12);
+
+        // the keys of both parts of the puzzle
+        let part1 = Key::Hands(vec![Hand::ROCK, Hand::PAPER, Hand::SCISSORS]);
+        let part2 = Key::Outcomes([Outcome::Loose, Outcome::Draw, Outcome::Win]);
+        let matching = analysis
+            .matching(15)
+            .into_iter()
+            .map(|interpretation| &interpretation.key)
+            .collect::<Vec<_>>();
+        assert!(matching.contains(&&part1));
+        assert!(analysis
+            .matching(12)
+            .iter()
+            .any(|interpretation| interpretation.key == part2));
+
+        // winning every round scores 6 + the played hands
+        let best = analysis.best().unwrap();
+        assert_eq!(best.score, 24);
+        assert_eq!(best.key.describe(&game), "X=Scissors Y=Paper Z=Rock");
+        // loosing every round only scores the played hands
+        assert_eq!(analysis.worst().unwrap().score, 6);
+    }
+
+    #[test]
+    fn test_analyze_invalid_guide() {
+        let game = CyclicGame::rock_paper_scissors();
+        assert_eq!(
+            analyze(&game, &ScoringRules::standard(&game), "A X\nA W"),
+            Err(KeyError::InvalidLine(2))
+        );
+
+        let game = CyclicGame::new((0..13).map(|i| i.to_string())).unwrap();
+        let error = analyze(&game, &ScoringRules::standard(&game), "A Z").unwrap_err();
+        assert_eq!(error, KeyError::TooManyHands(13));
+        assert_eq!(
+            error.to_string(),
+            "a game with 13 hands has too many keys (at most 9 hands)"
+        );
+    }
+
+    #[test]
+    fn test_describe() {
+        let game = CyclicGame::rock_paper_scissors();
+        assert_eq!(
+            Key::Hands(vec![Hand::PAPER, Hand::ROCK, Hand::SCISSORS]).describe(&game),
+            "X=Paper Y=Rock Z=Scissors"
+        );
+        assert_eq!(
+            Key::Outcomes([Outcome::Win, Outcome::Draw, Outcome::Loose]).describe(&game),
+            "X=win Y=draw Z=loss"
+        );
+    }
+}
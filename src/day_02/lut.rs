@@ -0,0 +1,102 @@
+/// Scores of part 1, indexed by `3 * enemy + we` with both codes counted from 0.
+const PART1: [u32; 9] = part1_table();
+/// Scores of part 2, indexed by `3 * enemy + outcome` with both codes counted from 0.
+const PART2: [u32; 9] = part2_table();
+
+const fn part1_table() -> [u32; 9] {
+    let mut table = [0; 9];
+    let mut i = 0;
+    while i < 9 {
+        let (enemy, we) = (i / 3, i % 3);
+        // 0 is a draw, 1 a win and 2 a loss
+        let outcome = match (we + 3 - enemy) % 3 {
+            0 => 3,
+            1 => 6,
+            _ => 0,
+        };
+        table[i] = we as u32 + 1 + outcome;
+        i += 1;
+    }
+    table
+}
+
+const fn part2_table() -> [u32; 9] {
+    let mut table = [0; 9];
+    let mut i = 0;
+    while i < 9 {
+        let (enemy, outcome) = (i / 3, i % 3);
+        // loosing is the hand before the enemy's, winning the one after it
+        let we = (enemy + outcome + 2) % 3;
+        table[i] = we as u32 + 1 + 3 * outcome as u32;
+        i += 1;
+    }
+    table
+}
+
+/// Solve both parts of the rock-paper-scissors guide in a single pass over the raw input.
+///
+/// Every line has to be a code `A..C`, a single space and a code `X..Z`, optionally ending with
+/// `\r`. Returns `None` for any other line, including empty ones before the end of the input.
+/// Nothing is allocated.
+pub fn solve(input: &[u8]) -> Option<(u32, u32)> {
+    let input = input.strip_suffix(b"\n").unwrap_or(input);
+    if input.is_empty() {
+        return Some((0, 0));
+    }
+
+    let mut scores = (0, 0);
+    for line in input.split(|&byte| byte == b'\n') {
+        match line.strip_suffix(b"\r").unwrap_or(line) {
+            &[enemy @ b'A'..=b'C', b' ', we @ b'X'..=b'Z'] => {
+                let index = 3 * (enemy - b'A') as usize + (we - b'X') as usize;
+                scores.0 += PART1[index];
+                scores.1 += PART2[index];
+            }
+            _ => return None,
+        }
+    }
+    Some(scores)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::day_02::{
+        game::CyclicGame, parse_hands, parse_outcomes, score_hands, score_outcomes,
+        scoring::ScoringRules,
+    };
+
+    #[test]
+    fn test_solve() {
+        assert_eq!(solve(b"A Y\nB X\nC Z"), Some((15, 12)));
+        assert_eq!(solve(b"A Y\r\nB X\r\nC Z\r\n"), Some((15, 12)));
+        assert_eq!(solve(b""), Some((0, 0)));
+    }
+
+    #[test]
+    fn test_solve_malformed() {
+        assert_eq!(solve(b"A Q\nB X"), None);
+        assert_eq!(solve(b"AB X"), None);
+        assert_eq!(solve(b"A Y\n\nB X"), None);
+        assert_eq!(solve(b"A Y Z"), None);
+        assert_eq!(solve(b"A"), None);
+    }
+
+    #[test]
+    fn test_solve_matches_generic_path() {
+        // every combination of codes
+        let input = ["A", "B", "C"]
+            .iter()
+            .flat_map(|enemy| ["X", "Y", "Z"].map(|code| format!("{enemy} {code}")))
+            .collect::<Vec<_>>()
+            .join("\n");
+
+        let game = CyclicGame::rock_paper_scissors();
+        let rules = ScoringRules::standard(&game);
+        let part1 = score_hands(&game, &rules, &parse_hands(&game, &input));
+        let part2 = score_outcomes(&game, &rules, &parse_outcomes(&game, &input));
+
+        let (fast1, fast2) = solve(input.as_bytes()).unwrap();
+        assert_eq!((fast1 as i64, fast2 as i64), (part1, part2));
+    }
+}
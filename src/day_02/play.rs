@@ -0,0 +1,274 @@
+use std::{
+    cmp::Ordering,
+    fmt,
+    io::{self, BufRead, Write},
+    str::FromStr,
+};
+
+use super::{
+    game::{CyclicGame, Hand, Outcome},
+    scoring::ScoringRules,
+    tournament::{
+        FixedGuide, FrequencyCounter, MarkovPredictor, ReplayLast, SeededRandom, Strategy,
+    },
+};
+
+/// Strategy of the opponent of a human player.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Opponent {
+    /// Play the enemy's hands of the strategy guide.
+    Guide,
+    Random,
+    Frequency,
+    Markov,
+    Replay,
+}
+
+impl Opponent {
+    /// Create the strategy, `guide` holds the rounds of the strategy guide as (enemy, we).
+    pub fn strategy(self, seed: u64, guide: &[(Hand, Hand)]) -> Box<dyn Strategy> {
+        match self {
+            Opponent::Guide => Box::new(FixedGuide::new(
+                "guide",
+                guide.iter().map(|&(enemy, _)| enemy).collect(),
+            )),
+            Opponent::Random => Box::new(SeededRandom::new(seed)),
+            Opponent::Frequency => Box::new(FrequencyCounter),
+            Opponent::Markov => Box::new(MarkovPredictor),
+            Opponent::Replay => Box::new(ReplayLast),
+        }
+    }
+}
+
+impl FromStr for Opponent {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "guide" => Ok(Opponent::Guide),
+            "random" => Ok(Opponent::Random),
+            "frequency" => Ok(Opponent::Frequency),
+            "markov" => Ok(Opponent::Markov),
+            "replay" => Ok(Opponent::Replay),
+            _ => Err(format!(
+                "unknown opponent '{s}' (expected guide, random, frequency, markov or replay)"
+            )),
+        }
+    }
+}
+
+/// Result of a session of a human against an opponent.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct Summary {
+    pub rounds: usize,
+    pub wins: usize,
+    pub draws: usize,
+    pub losses: usize,
+    pub score: i64,
+    /// Score the hands of the strategy guide would have achieved against the same opponent hands,
+    /// if there is a guide.
+    pub guide_score: Option<i64>,
+}
+
+impl fmt::Display for Summary {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(
+            f,
+            "{} rounds: {} wins, {} draws, {} losses",
+            self.rounds, self.wins, self.draws, self.losses
+        )?;
+        writeln!(f, "your score:  {}", self.score)?;
+        if let Some(guide) = self.guide_score {
+            writeln!(f, "guide score: {guide}")?;
+            match self.score.cmp(&guide) {
+                Ordering::Greater => writeln!(f, "you beat the guide by {}", self.score - guide)?,
+                Ordering::Less => writeln!(f, "the guide beat you by {}", guide - self.score)?,
+                Ordering::Equal => writeln!(f, "you tied with the guide")?,
+            }
+        }
+        Ok(())
+    }
+}
+
+/// A human playing against an opponent strategy.
+pub struct Session<'a> {
+    game: &'a CyclicGame,
+    rules: &'a ScoringRules,
+    opponent: Box<dyn Strategy>,
+    /// Our hands of the strategy guide, played in order to compare against.
+    guide: Vec<Hand>,
+    /// Rounds from the perspective of the opponent.
+    history: Vec<(Hand, Hand)>,
+    summary: Summary,
+}
+
+impl<'a> Session<'a> {
+    /// Start a session, `guide` holds the rounds of the strategy guide as (enemy, we).
+    pub fn new(
+        game: &'a CyclicGame,
+        rules: &'a ScoringRules,
+        opponent: Box<dyn Strategy>,
+        guide: &[(Hand, Hand)],
+    ) -> Session<'a> {
+        Session {
+            game,
+            rules,
+            opponent,
+            guide: guide.iter().map(|&(_, we)| we).collect(),
+            history: vec![],
+            summary: Summary {
+                guide_score: (!guide.is_empty()).then_some(0),
+                ..Summary::default()
+            },
+        }
+    }
+
+    /// Play a round with the given hand, returning the opponent's hand and the outcome.
+    pub fn play_round(&mut self, hand: Hand) -> (Hand, Outcome) {
+        let theirs = self.opponent.play(self.game, &self.history);
+        self.history.push((theirs, hand));
+
+        let outcome = self.game.beats(hand, theirs);
+        match outcome {
+            Outcome::Win => self.summary.wins += 1,
+            Outcome::Draw => self.summary.draws += 1,
+            Outcome::Loose => self.summary.losses += 1,
+        }
+        self.summary.score += self.rules.score(self.game, theirs, hand);
+        if let Some(score) = &mut self.summary.guide_score {
+            let guide = self.guide[self.summary.rounds % self.guide.len()];
+            *score += self.rules.score(self.game, theirs, guide);
+        }
+        self.summary.rounds += 1;
+        (theirs, outcome)
+    }
+
+    pub fn summary(&self) -> &Summary {
+        &self.summary
+    }
+
+    /// Read hands from `input` until it ends or the player quits, reporting every round to
+    /// `output`.
+    ///
+    /// Hands are entered by their name, a unique prefix of it, or their code in the guide.
+    pub fn run(mut self, input: impl BufRead, mut output: impl Write) -> io::Result<Summary> {
+        let names = self
+            .game
+            .hands()
+            .map(|hand| self.game.name(hand))
+            .collect::<Vec<_>>()
+            .join(", ");
+        writeln!(output, "Play {names} (or quit).")?;
+
+        let mut lines = input.lines();
+        loop {
+            write!(output, "round {}> ", self.summary.rounds + 1)?;
+            output.flush()?;
+            let Some(line) = lines.next().transpose()? else {
+                writeln!(output)?;
+                break;
+            };
+
+            let line = line.trim();
+            if line.is_empty() {
+                continue;
+            }
+            if ["quit", "q", "exit"].contains(&line) {
+                break;
+            }
+            let Some(hand) = self.parse_hand(line) else {
+                writeln!(output, "unknown hand '{line}', play one of {names}")?;
+                continue;
+            };
+
+            let (theirs, outcome) = self.play_round(hand);
+            write!(
+                output,
+                "{} against {}: {outcome}, score {}",
+                self.game.name(hand),
+                self.game.name(theirs),
+                self.summary.score
+            )?;
+            match self.summary.guide_score {
+                Some(guide) => writeln!(output, " (guide {guide})")?,
+                None => writeln!(output)?,
+            }
+        }
+
+        write!(output, "{}", self.summary)?;
+        Ok(self.summary)
+    }
+
+    fn parse_hand(&self, line: &str) -> Option<Hand> {
+        if let Some(hand) = self.game.hand(line).or(self.game.own_code(line)) {
+            return Some(hand);
+        }
+        // a unique prefix of a name
+        let mut candidates = self.game.hands().filter(|&hand| {
+            self.game
+                .name(hand)
+                .to_lowercase()
+                .starts_with(&line.to_lowercase())
+        });
+        match (candidates.next(), candidates.next()) {
+            (Some(hand), None) => Some(hand),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const GUIDE: [(Hand, Hand); 3] = [
+        (Hand::ROCK, Hand::PAPER),
+        (Hand::PAPER, Hand::ROCK),
+        (Hand::SCISSORS, Hand::SCISSORS),
+    ];
+
+    #[test]
+    fn test_play_against_guide() {
+        let game = CyclicGame::rock_paper_scissors();
+        let rules = ScoringRules::standard(&game);
+        let session = Session::new(&game, &rules, Opponent::Guide.strategy(0, &GUIDE), &GUIDE);
+
+        let mut output = vec![];
+        let summary = session
+            .run("paper\nspock\nS\n\nZ\nquit\nrock\n".as_bytes(), &mut output)
+            .unwrap();
+
+        // paper beats rock, scissors beat paper, scissors draw
+        assert_eq!(
+            summary,
+            Summary {
+                rounds: 3,
+                wins: 2,
+                draws: 1,
+                losses: 0,
+                score: 8 + 9 + 6,
+                guide_score: Some(15),
+            }
+        );
+
+        let output = String::from_utf8(output).unwrap();
+        assert!(output.contains("unknown hand 'spock'"));
+        assert!(output.contains("Scissors against Paper: win, score 17 (guide 9)\n"));
+        assert!(output.ends_with("you beat the guide by 8\n"));
+    }
+
+    #[test]
+    fn test_play_without_guide() {
+        let game = CyclicGame::rock_paper_scissors_lizard_spock();
+        let rules = ScoringRules::standard(&game);
+        let mut session = Session::new(&game, &rules, Opponent::Replay.strategy(0, &[]), &[]);
+
+        // replaying our last hand always draws after the first round
+        let spock = game.hand("spock").unwrap();
+        assert_eq!(session.play_round(spock), (Hand::ROCK, Outcome::Win));
+        assert_eq!(session.play_round(spock), (spock, Outcome::Draw));
+        assert_eq!(session.summary().guide_score, None);
+        assert_eq!(session.parse_hand("li"), game.hand("lizard"));
+        assert_eq!(session.parse_hand("s"), None);
+    }
+}
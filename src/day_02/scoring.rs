@@ -0,0 +1,165 @@
+use std::{collections::HashMap, error::Error, fmt, fs, io, path::Path};
+
+use serde::Deserialize;
+
+use super::game::{CyclicGame, Hand, Outcome};
+
+/// Points awarded for the hand played and the outcome of a round.
+///
+/// Written as TOML, every entry is optional and defaults to the rules of the puzzle:
+///
+/// ```toml
+/// [hands]
+/// rock = 1
+/// paper = 2
+/// scissors = 3
+///
+/// [outcomes]
+/// loss = 0
+/// draw = 3
+/// win = 6
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ScoringRules {
+    /// Points of every hand, indexed by the hand.
+    hands: Vec<i64>,
+    loss: i64,
+    draw: i64,
+    win: i64,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+struct RulesFile {
+    hands: HashMap<String, i64>,
+    outcomes: OutcomesFile,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+struct OutcomesFile {
+    loss: Option<i64>,
+    draw: Option<i64>,
+    win: Option<i64>,
+}
+
+impl ScoringRules {
+    /// Rules of the puzzle: a hand is worth its position in the cycle counted from 1, a loss 0,
+    /// a draw 3 and a win 6 points.
+    pub fn standard(game: &CyclicGame) -> ScoringRules {
+        ScoringRules {
+            hands: game.hands().map(|hand| hand.index() as i64 + 1).collect(),
+            loss: Outcome::Loose.val(),
+            draw: Outcome::Draw.val(),
+            win: Outcome::Win.val(),
+        }
+    }
+
+    /// Parse rules for the given game, hands are referred to by their name ignoring case.
+    pub fn from_toml(game: &CyclicGame, toml: &str) -> Result<ScoringRules, RulesError> {
+        let file = toml::from_str::<RulesFile>(toml).map_err(RulesError::Parse)?;
+
+        let mut rules = ScoringRules::standard(game);
+        for (name, points) in file.hands {
+            let hand = game.hand(&name).ok_or(RulesError::UnknownHand(name))?;
+            rules.hands[hand.index()] = points;
+        }
+        rules.loss = file.outcomes.loss.unwrap_or(rules.loss);
+        rules.draw = file.outcomes.draw.unwrap_or(rules.draw);
+        rules.win = file.outcomes.win.unwrap_or(rules.win);
+        Ok(rules)
+    }
+
+    /// Load rules for the given game from a TOML file.
+    pub fn load(game: &CyclicGame, path: &Path) -> Result<ScoringRules, RulesError> {
+        let content = fs::read_to_string(path).map_err(RulesError::Io)?;
+        ScoringRules::from_toml(game, &content)
+    }
+
+    /// Points for playing the given hand.
+    pub fn hand(&self, hand: Hand) -> i64 {
+        self.hands[hand.index()]
+    }
+
+    /// Points for the given outcome.
+    pub fn outcome(&self, outcome: Outcome) -> i64 {
+        match outcome {
+            Outcome::Loose => self.loss,
+            Outcome::Draw => self.draw,
+            Outcome::Win => self.win,
+        }
+    }
+
+    /// Score of playing `we` against `enemy`.
+    pub fn score(&self, game: &CyclicGame, enemy: Hand, we: Hand) -> i64 {
+        self.hand(we) + self.outcome(game.beats(we, enemy))
+    }
+}
+
+/// Error while reading scoring rules.
+#[derive(Debug)]
+pub enum RulesError {
+    Io(io::Error),
+    Parse(toml::de::Error),
+    /// A hand which is not part of the game.
+    UnknownHand(String),
+}
+
+impl fmt::Display for RulesError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RulesError::Io(error) => write!(f, "could not read scoring rules: {error}"),
+            RulesError::Parse(error) => write!(f, "invalid scoring rules: {error}"),
+            RulesError::UnknownHand(name) => write!(f, "the game has no hand '{name}'"),
+        }
+    }
+}
+
+impl Error for RulesError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_standard_rules() {
+        let game = CyclicGame::rock_paper_scissors();
+        let rules = ScoringRules::standard(&game);
+        assert_eq!(rules.score(&game, Hand::ROCK, Hand::PAPER), 8);
+        assert_eq!(rules.score(&game, Hand::PAPER, Hand::ROCK), 1);
+        assert_eq!(rules.score(&game, Hand::SCISSORS, Hand::SCISSORS), 6);
+        assert_eq!(ScoringRules::from_toml(&game, "").unwrap(), rules);
+    }
+
+    #[test]
+    fn test_rules_from_toml() {
+        let game = CyclicGame::rock_paper_scissors();
+        let rules = ScoringRules::from_toml(
+            &game,
+            r#"
+            hands = { Scissors = 10 }
+            outcomes = { loss = -5, win = 2 }
+            "#,
+        )
+        .unwrap();
+
+        assert_eq!(rules.hand(Hand::ROCK), 1);
+        assert_eq!(rules.hand(Hand::SCISSORS), 10);
+        assert_eq!(rules.outcome(Outcome::Loose), -5);
+        assert_eq!(rules.outcome(Outcome::Draw), 3);
+        assert_eq!(rules.score(&game, Hand::ROCK, Hand::SCISSORS), 5);
+    }
+
+    #[test]
+    fn test_rules_errors() {
+        let game = CyclicGame::rock_paper_scissors();
+        assert!(matches!(
+            ScoringRules::from_toml(&game, "hands = { spock = 4 }"),
+            Err(RulesError::UnknownHand(name)) if name == "spock"
+        ));
+        assert!(matches!(
+            ScoringRules::from_toml(&game, "points = 3"),
+            Err(RulesError::Parse(_))
+        ));
+    }
+}
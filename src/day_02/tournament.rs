@@ -0,0 +1,486 @@
+use std::{
+    cmp::{Ordering, Reverse},
+    fmt,
+};
+
+use super::{
+    game::{CyclicGame, Hand, Outcome},
+    scoring::ScoringRules,
+};
+
+/// A way of choosing hands in a match.
+pub trait Strategy {
+    fn name(&self) -> String;
+
+    /// Choose the hand of the next round from the rounds played so far in this match, as pairs
+    /// of our and the opponent's hand.
+    fn play(&mut self, game: &CyclicGame, history: &[(Hand, Hand)]) -> Hand;
+}
+
+/// Play the hands of a strategy guide in order, starting over once it is exhausted.
+#[derive(Debug, Clone)]
+pub struct FixedGuide {
+    name: String,
+    hands: Vec<Hand>,
+}
+
+impl FixedGuide {
+    pub fn new(name: impl Into<String>, hands: Vec<Hand>) -> FixedGuide {
+        FixedGuide {
+            name: name.into(),
+            hands,
+        }
+    }
+}
+
+impl Strategy for FixedGuide {
+    fn name(&self) -> String {
+        self.name.clone()
+    }
+
+    fn play(&mut self, _: &CyclicGame, history: &[(Hand, Hand)]) -> Hand {
+        match self.hands.is_empty() {
+            true => Hand::ROCK,
+            false => self.hands[history.len() % self.hands.len()],
+        }
+    }
+}
+
+/// Play uniformly random hands from a seeded SplitMix64 generator, so tournaments are
+/// reproducible.
+#[derive(Debug, Clone)]
+pub struct SeededRandom {
+    seed: u64,
+    state: u64,
+}
+
+impl SeededRandom {
+    pub fn new(seed: u64) -> SeededRandom {
+        SeededRandom { seed, state: seed }
+    }
+
+    fn next(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    }
+}
+
+impl Strategy for SeededRandom {
+    fn name(&self) -> String {
+        format!("random ({})", self.seed)
+    }
+
+    fn play(&mut self, game: &CyclicGame, _: &[(Hand, Hand)]) -> Hand {
+        let index = (self.next() % game.len() as u64) as usize;
+        game.hand_at(index)
+            .expect("the index is below the number of hands")
+    }
+}
+
+/// Beat the hand the opponent played most often so far.
+#[derive(Debug, Clone, Default)]
+pub struct FrequencyCounter;
+
+impl Strategy for FrequencyCounter {
+    fn name(&self) -> String {
+        "frequency".to_string()
+    }
+
+    fn play(&mut self, game: &CyclicGame, history: &[(Hand, Hand)]) -> Hand {
+        let predicted = most_frequent(game, history.iter().map(|&(_, theirs)| theirs));
+        game.with_outcome_against(predicted, Outcome::Win)
+    }
+}
+
+/// Predict the opponent's next hand from the hands which followed its last one so far, and beat
+/// it.
+#[derive(Debug, Clone, Default)]
+pub struct MarkovPredictor;
+
+impl Strategy for MarkovPredictor {
+    fn name(&self) -> String {
+        "markov".to_string()
+    }
+
+    fn play(&mut self, game: &CyclicGame, history: &[(Hand, Hand)]) -> Hand {
+        let Some(&(_, last)) = history.last() else {
+            return Hand::ROCK;
+        };
+        let followers = history
+            .windows(2)
+            .filter(|rounds| rounds[0].1 == last)
+            .map(|rounds| rounds[1].1);
+        let predicted = most_frequent(game, followers);
+        game.with_outcome_against(predicted, Outcome::Win)
+    }
+}
+
+/// Play the hand the opponent played in the last round.
+#[derive(Debug, Clone, Default)]
+pub struct ReplayLast;
+
+impl Strategy for ReplayLast {
+    fn name(&self) -> String {
+        "replay last".to_string()
+    }
+
+    fn play(&mut self, _: &CyclicGame, history: &[(Hand, Hand)]) -> Hand {
+        history.last().map_or(Hand::ROCK, |&(_, theirs)| theirs)
+    }
+}
+
+/// Most frequent of the given hands, the first hand of the game if there are none. Of equally
+/// frequent hands, the first one in the game is chosen.
+fn most_frequent(game: &CyclicGame, hands: impl Iterator<Item = Hand>) -> Hand {
+    let mut counts = vec![0; game.len()];
+    for hand in hands {
+        counts[hand.index()] += 1;
+    }
+    let most = counts.iter().copied().max().unwrap_or(0);
+    counts
+        .iter()
+        .position(|&count| count == most)
+        .and_then(|index| game.hand_at(index))
+        .unwrap_or(Hand::ROCK)
+}
+
+/// Result of a match between two players, identified by their index in the tournament.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MatchResult {
+    pub players: (usize, usize),
+    pub scores: (i64, i64),
+    /// Rounds won by each player.
+    pub wins: (usize, usize),
+    pub draws: usize,
+}
+
+impl MatchResult {
+    /// The player with the higher score, `None` on a tie.
+    pub fn winner(&self) -> Option<usize> {
+        match self.scores.0.cmp(&self.scores.1) {
+            Ordering::Greater => Some(self.players.0),
+            Ordering::Less => Some(self.players.1),
+            Ordering::Equal => None,
+        }
+    }
+}
+
+/// Plays matches of a fixed number of rounds under the day 2 scoring.
+pub struct Tournament<'a> {
+    game: &'a CyclicGame,
+    rules: &'a ScoringRules,
+    rounds: usize,
+}
+
+impl<'a> Tournament<'a> {
+    pub fn new(game: &'a CyclicGame, rules: &'a ScoringRules, rounds: usize) -> Tournament<'a> {
+        Tournament {
+            game,
+            rules,
+            rounds,
+        }
+    }
+
+    /// Play a single match between the players with the given indices.
+    pub fn play_match(
+        &self,
+        players: &mut [Box<dyn Strategy>],
+        first: usize,
+        second: usize,
+    ) -> MatchResult {
+        // every player sees the history from its own perspective
+        let mut history = (vec![], vec![]);
+        let mut result = MatchResult {
+            players: (first, second),
+            scores: (0, 0),
+            wins: (0, 0),
+            draws: 0,
+        };
+
+        for _ in 0..self.rounds {
+            let ours = players[first].play(self.game, &history.0);
+            let theirs = players[second].play(self.game, &history.1);
+            history.0.push((ours, theirs));
+            history.1.push((theirs, ours));
+
+            result.scores.0 += self.rules.score(self.game, theirs, ours);
+            result.scores.1 += self.rules.score(self.game, ours, theirs);
+            match self.game.beats(ours, theirs) {
+                Outcome::Win => result.wins.0 += 1,
+                Outcome::Loose => result.wins.1 += 1,
+                Outcome::Draw => result.draws += 1,
+            }
+        }
+        result
+    }
+
+    /// Let every player play against every other player once.
+    pub fn round_robin(&self, players: &mut [Box<dyn Strategy>]) -> TournamentResult {
+        let mut matches = vec![];
+        for first in 0..players.len() {
+            for second in first + 1..players.len() {
+                matches.push(self.play_match(players, first, second));
+            }
+        }
+        TournamentResult {
+            names: players.iter().map(|player| player.name()).collect(),
+            matches,
+            champion: None,
+        }
+    }
+
+    /// Play a single elimination bracket, seeded by the order of the players.
+    ///
+    /// Without an opponent, a player advances to the next round. A tied match is decided by the
+    /// rounds won and then by the seed.
+    pub fn elimination(&self, players: &mut [Box<dyn Strategy>]) -> TournamentResult {
+        let mut matches = vec![];
+        let mut remaining = (0..players.len()).collect::<Vec<_>>();
+        while remaining.len() > 1 {
+            let mut next = vec![];
+            for pair in remaining.chunks(2) {
+                let &[first, second] = pair else {
+                    next.push(pair[0]);
+                    continue;
+                };
+                let result = self.play_match(players, first, second);
+                let winner = result
+                    .winner()
+                    .unwrap_or(match result.wins.1 > result.wins.0 {
+                        true => second,
+                        false => first,
+                    });
+                next.push(winner);
+                matches.push(result);
+            }
+            remaining = next;
+        }
+        TournamentResult {
+            names: players.iter().map(|player| player.name()).collect(),
+            matches,
+            champion: remaining.first().copied(),
+        }
+    }
+}
+
+/// Standing of a player after a tournament.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Standing {
+    pub player: usize,
+    pub name: String,
+    pub won: usize,
+    pub drawn: usize,
+    pub lost: usize,
+    /// Total score over all matches.
+    pub score: i64,
+}
+
+/// All matches played in a tournament.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TournamentResult {
+    pub names: Vec<String>,
+    pub matches: Vec<MatchResult>,
+    /// Winner of an elimination tournament.
+    pub champion: Option<usize>,
+}
+
+impl TournamentResult {
+    /// Total score of every player, indexed by the player.
+    pub fn totals(&self) -> Vec<i64> {
+        let mut totals = vec![0; self.names.len()];
+        for result in &self.matches {
+            totals[result.players.0] += result.scores.0;
+            totals[result.players.1] += result.scores.1;
+        }
+        totals
+    }
+
+    /// Get the standings, ordered by matches won, then by total score.
+    pub fn standings(&self) -> Vec<Standing> {
+        let totals = self.totals();
+        let mut standings = self
+            .names
+            .iter()
+            .enumerate()
+            .map(|(player, name)| Standing {
+                player,
+                name: name.clone(),
+                won: 0,
+                drawn: 0,
+                lost: 0,
+                score: totals[player],
+            })
+            .collect::<Vec<_>>();
+
+        for result in &self.matches {
+            let (first, second) = result.players;
+            match result.winner() {
+                Some(winner) => {
+                    let looser = if winner == first { second } else { first };
+                    standings[winner].won += 1;
+                    standings[looser].lost += 1;
+                }
+                None => {
+                    standings[first].drawn += 1;
+                    standings[second].drawn += 1;
+                }
+            }
+        }
+
+        standings.sort_by_key(|standing| {
+            (
+                Reverse(standing.won),
+                Reverse(standing.score),
+                standing.player,
+            )
+        });
+        standings
+    }
+
+    /// Score every player made against every other player, `None` if they have not met.
+    pub fn head_to_head(&self) -> Vec<Vec<Option<i64>>> {
+        let mut matrix = vec![vec![None; self.names.len()]; self.names.len()];
+        for result in &self.matches {
+            let (first, second) = result.players;
+            *matrix[first][second].get_or_insert(0) += result.scores.0;
+            *matrix[second][first].get_or_insert(0) += result.scores.1;
+        }
+        matrix
+    }
+}
+
+impl fmt::Display for TournamentResult {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let width = self.names.iter().map(String::len).max().unwrap_or(0);
+
+        writeln!(
+            f,
+            "{:<width$}  {:>3} {:>3} {:>3} {:>8}",
+            "", "W", "D", "L", "score"
+        )?;
+        for standing in self.standings() {
+            writeln!(
+                f,
+                "{:<width$}  {:>3} {:>3} {:>3} {:>8}",
+                standing.name, standing.won, standing.drawn, standing.lost, standing.score
+            )?;
+        }
+        if let Some(champion) = self.champion {
+            writeln!(f, "champion: {}", self.names[champion])?;
+        }
+
+        // columns are numbered from 1 for humans, in the order of the rows
+        writeln!(f)?;
+        write!(f, "{:<width$}", "")?;
+        for column in 1..=self.names.len() {
+            write!(f, " {column:>8}")?;
+        }
+        writeln!(f)?;
+        for (name, row) in self.names.iter().zip(self.head_to_head()) {
+            write!(f, "{name:<width$}")?;
+            for score in row {
+                match score {
+                    Some(score) => write!(f, " {score:>8}")?,
+                    None => write!(f, " {:>8}", "-")?,
+                }
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn players() -> Vec<Box<dyn Strategy>> {
+        vec![
+            Box::new(FixedGuide::new("rock", vec![Hand::ROCK])),
+            Box::new(FixedGuide::new("paper", vec![Hand::PAPER])),
+            Box::new(FrequencyCounter),
+            Box::new(ReplayLast),
+        ]
+    }
+
+    #[test]
+    fn test_play_match() {
+        let game = CyclicGame::rock_paper_scissors();
+        let rules = ScoringRules::standard(&game);
+        let tournament = Tournament::new(&game, &rules, 10);
+
+        let result = tournament.play_match(&mut players(), 0, 1);
+        assert_eq!(result.scores, (10, 80));
+        assert_eq!(result.wins, (0, 10));
+        assert_eq!(result.winner(), Some(1));
+
+        // without any history, the frequency counter plays paper as well
+        let result = tournament.play_match(&mut players(), 2, 1);
+        assert_eq!(result.wins, (9, 0));
+        assert_eq!(result.draws, 1);
+    }
+
+    #[test]
+    fn test_markov_predictor() {
+        let game = CyclicGame::rock_paper_scissors();
+        let history = [
+            (Hand::ROCK, Hand::ROCK),
+            (Hand::ROCK, Hand::PAPER),
+            (Hand::ROCK, Hand::ROCK),
+        ];
+        // after rock the opponent played paper, so play scissors
+        assert_eq!(MarkovPredictor.play(&game, &history), Hand::SCISSORS);
+        assert_eq!(MarkovPredictor.play(&game, &[]), Hand::ROCK);
+    }
+
+    #[test]
+    fn test_seeded_random() {
+        let game = CyclicGame::rock_paper_scissors_lizard_spock();
+        let hands = |seed| {
+            let mut random = SeededRandom::new(seed);
+            (0..20).map(|_| random.play(&game, &[])).collect::<Vec<_>>()
+        };
+        assert_eq!(hands(7), hands(7));
+        assert_ne!(hands(7), hands(8));
+        assert!(hands(7).iter().all(|hand| hand.index() < 5));
+    }
+
+    #[test]
+    fn test_round_robin() {
+        let game = CyclicGame::rock_paper_scissors();
+        let rules = ScoringRules::standard(&game);
+        let result = Tournament::new(&game, &rules, 10).round_robin(&mut players());
+
+        assert_eq!(result.matches.len(), 6);
+        let matrix = result.head_to_head();
+        assert_eq!(matrix[0][1], Some(10));
+        assert_eq!(matrix[1][0], Some(80));
+        assert_eq!(matrix[0][0], None);
+        assert_eq!(
+            result.totals().iter().sum::<i64>(),
+            result
+                .matches
+                .iter()
+                .map(|m| m.scores.0 + m.scores.1)
+                .sum::<i64>()
+        );
+        assert_eq!(result.standings()[0].name, "frequency");
+    }
+
+    #[test]
+    fn test_elimination() {
+        let game = CyclicGame::rock_paper_scissors();
+        let rules = ScoringRules::standard(&game);
+        let mut players = players();
+        players.push(Box::new(SeededRandom::new(1)));
+        let result = Tournament::new(&game, &rules, 10).elimination(&mut players);
+
+        // 5 players need 4 matches to find a champion
+        assert_eq!(result.matches.len(), 4);
+        assert!(result.champion.is_some());
+        assert!(result.to_string().contains("champion: "));
+    }
+}
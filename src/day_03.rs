@@ -1,73 +1,25 @@
-use std::collections::HashSet;
-
 use aoc_runner_derive::{aoc, aoc_generator};
 
-/// A struct representing a rucksack with two compartements.
-#[derive(Debug, Clone, PartialEq, Eq)]
-struct Rucksack(String, String);
+use crate::solver::{Part, Run};
 
-#[derive(Debug, Clone, PartialEq, Eq)]
-struct ElveGroup(String, String, String);
+pub mod audit;
+pub mod group;
+pub mod item_set;
+pub mod priority;
+pub mod reorganize;
 
-trait Priority {
-    /// Get the priority of this item for the elves.
-    fn to_prio(self) -> u32;
-}
+use group::{ElveGroup, Grouping};
+use item_set::ItemSet;
+use priority::{Alphabet, ItemError};
 
-impl Priority for char {
-    fn to_prio(self) -> u32 {
-        if ('a'..='z').contains(&self) {
-            self as u32 - 'a' as u32 + 1
-        } else {
-            self as u32 - 'A' as u32 + 27
-        }
-    }
-}
+/// A struct representing a rucksack with two compartements.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Rucksack(String, String);
 
 impl Rucksack {
     /// Find all elements which are in both compartements.
-    pub fn find_duplicates(&self) -> Vec<char> {
-        let mut duplicates = HashSet::new();
-
-        let mut chars_set = HashSet::new();
-        for c in self.0.chars() {
-            chars_set.insert(c);
-        }
-
-        for c in self.1.chars() {
-            if chars_set.get(&c).is_some() {
-                duplicates.insert(c);
-            }
-        }
-        duplicates.into_iter().collect()
-    }
-}
-
-impl ElveGroup {
-    /// Find all elements which are in the rucksacks of all three elves.
-    pub fn find_duplicates(&self) -> Vec<char> {
-        let mut duplicates = HashSet::new();
-
-        let mut chars_set = HashSet::new();
-        for c in self.0.chars() {
-            chars_set.insert(c);
-        }
-
-        for c in self.1.chars() {
-            if chars_set.get(&c).is_some() {
-                duplicates.insert(c);
-            }
-        }
-
-        chars_set = duplicates;
-        duplicates = HashSet::new();
-        for c in self.2.chars() {
-            if chars_set.get(&c).is_some() {
-                duplicates.insert(c);
-            }
-        }
-
-        duplicates.into_iter().collect()
+    pub fn find_duplicates(&self) -> ItemSet {
+        ItemSet::from_valid(&self.0).intersection(ItemSet::from_valid(&self.1))
     }
 }
 
@@ -75,48 +27,38 @@ impl ElveGroup {
 /// It packs the items of all elves into their respective compartements.
 #[aoc_generator(day3, part1)]
 fn generator_day03_part1(inp: &str) -> Vec<Rucksack> {
+    try_generator_day03_part1(inp).unwrap_or_else(|error| panic!("invalid input: {error}"))
+}
+
+fn try_generator_day03_part1(inp: &str) -> Result<Vec<Rucksack>, ItemError> {
+    Alphabet::standard().validate(inp)?;
     let mut rucksacks = vec![];
     for line in inp.lines() {
+        // all items are ASCII, so the middle is a character boundary
         let (left, right) = line.split_at(line.len() / 2);
         rucksacks.push(Rucksack(left.to_string(), right.to_string()));
     }
-    rucksacks
+    Ok(rucksacks)
 }
 
 /// Generator for part 2 of day 3.
 /// It groups three elves as one "ElveGroup".
 #[aoc_generator(day3, part2)]
 fn generator_day03_part2(inp: &str) -> Vec<ElveGroup> {
-    let mut elve_groups = vec![];
-
-    let mut current_elves = vec![];
-
-    for line in inp.lines() {
-        current_elves.push(line);
-        if current_elves.len() == 3 {
-            elve_groups.push(ElveGroup(
-                current_elves[0].to_string(),
-                current_elves[1].to_string(),
-                current_elves[2].to_string(),
-            ));
-            current_elves.clear();
-        }
-    }
+    try_generator_day03_part2(inp).unwrap_or_else(|error| panic!("invalid input: {error}"))
+}
 
-    elve_groups
+fn try_generator_day03_part2(inp: &str) -> Result<Vec<ElveGroup>, ItemError> {
+    Alphabet::standard().validate(inp)?;
+    let rucksacks = inp.lines().collect::<Vec<_>>();
+    Ok(group::group(&rucksacks, &Grouping::Chunks(3)).expect("groups of three are valid"))
 }
 
 #[aoc(day3, part1)]
 fn day03_part1(rucksacks: &[Rucksack]) -> u32 {
     rucksacks
         .iter()
-        .map(|rucksack| {
-            rucksack
-                .find_duplicates()
-                .iter()
-                .map(|elem| elem.to_prio())
-                .sum::<u32>()
-        })
+        .map(|rucksack| rucksack.find_duplicates().priority_sum())
         .sum()
 }
 
@@ -126,14 +68,60 @@ fn day03_part2(elve_groups: &[ElveGroup]) -> u32 {
         .iter()
         .map(|elve_group| {
             elve_group
-                .find_duplicates()
-                .iter()
-                .map(|elem| elem.to_prio())
-                .sum::<u32>()
+                .badges()
+                .expect("the input is valid")
+                .priority_sum()
         })
         .sum()
 }
 
+/// Solve both parts for rucksacks packed with the items of the given alphabet.
+pub fn solve_with(alphabet: &Alphabet, inp: &str) -> Result<(u32, u32), ItemError> {
+    alphabet.validate(inp)?;
+    let set = |items: &str| alphabet.set(items).expect("the input is valid");
+
+    let mut part1 = 0;
+    for line in inp.lines() {
+        // split after half of the items, which are not necessarily a single byte
+        let middle = line
+            .char_indices()
+            .nth(line.chars().count() / 2)
+            .map_or(line.len(), |(index, _)| index);
+        let (left, right) = line.split_at(middle);
+        part1 += set(left)
+            .intersection(set(right))
+            .expect("both sets are of the same alphabet")
+            .priority_sum();
+    }
+
+    let rucksacks = inp.lines().collect::<Vec<_>>();
+    let part2 = group::group(&rucksacks, &Grouping::Chunks(3))
+        .expect("groups of three are valid")
+        .iter()
+        .map(|group| {
+            group
+                .badges_in(alphabet)
+                .expect("the input is valid")
+                .priority_sum()
+        })
+        .sum();
+    Ok((part1, part2))
+}
+
+/// Run the given part of this day for the provided input.
+pub(crate) fn run(part: Part, input: &str) -> Run {
+    match part {
+        Part::One => Run::measure(
+            || generator_day03_part1(input),
+            |rucksacks| day03_part1(rucksacks),
+        ),
+        Part::Two => Run::measure(
+            || generator_day03_part2(input),
+            |groups| day03_part2(groups),
+        ),
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -170,16 +158,16 @@ CrZsJsPPZsGzwwsLwLmpwMDw";
         assert_eq!(
             elve_groups,
             vec![
-                ElveGroup(
+                ElveGroup(vec![
                     "vJrwpWtwJgWrhcsFMMfFFhFp".to_string(),
                     "jqHRNqRjqzjGDLGLrsFMfFZSrLrFZsSL".to_string(),
                     "PmmdzqPrVvPwwTWBwg".to_string()
-                ),
-                ElveGroup(
+                ]),
+                ElveGroup(vec![
                     "wMqvLMZHhHMvwLHjbvcjnnSBnvTQFn".to_string(),
                     "ttgJtRGJQctTZtZT".to_string(),
                     "CrZsJsPPZsGzwwsLwLmpwMDw".to_string()
-                )
+                ])
             ]
         )
     }
@@ -188,25 +176,21 @@ CrZsJsPPZsGzwwsLwLmpwMDw";
     fn test_rucksack_find_duplicates() {
         assert_eq!(
             Rucksack("abcde".to_string(), "ABcDE".to_string()).find_duplicates(),
-            vec!['c']
+            ItemSet::try_from("c").unwrap()
         );
         assert_eq!(
             Rucksack("abcdaaa".to_string(), "ABCD".to_string()).find_duplicates(),
-            vec![]
+            ItemSet::new()
         );
     }
 
     #[test]
-    fn test_elve_group_find_duplicates() {
+    fn test_elve_group_badges() {
         let elve_groups = generator_day03_part2(INPUT);
         assert_eq!(
             elve_groups
                 .iter()
-                .map(|group| group
-                    .find_duplicates()
-                    .iter()
-                    .map(|elem| elem.to_prio())
-                    .sum::<u32>())
+                .map(|group| group.badges().unwrap().priority_sum())
                 .collect::<Vec<_>>(),
             vec![18, 52]
         );
@@ -223,4 +207,31 @@ CrZsJsPPZsGzwwsLwLmpwMDw";
         let elve_groups = generator_day03_part2(INPUT);
         assert_eq!(day03_part2(&elve_groups), 70);
     }
+
+    #[test]
+    fn test_generator_rejects_invalid_items() {
+        assert_eq!(
+            try_generator_day03_part1("abAB\nab1B"),
+            Err(ItemError { line: 2, item: '1' })
+        );
+        assert_eq!(
+            try_generator_day03_part2("abAB\nabcd\nab B"),
+            Err(ItemError { line: 3, item: ' ' })
+        );
+    }
+
+    #[test]
+    fn test_solve_with_alphabet() {
+        assert_eq!(solve_with(&Alphabet::standard(), INPUT), Ok((157, 70)));
+
+        let alphabet = Alphabet::new("🍎🍌🍒").unwrap();
+        assert_eq!(
+            solve_with(&alphabet, "🍎🍌🍒🍎\n🍌🍌\n🍒🍌"),
+            Ok((1 + 2, 2))
+        );
+        assert_eq!(
+            solve_with(&alphabet, "🍎x"),
+            Err(ItemError { line: 1, item: 'x' })
+        );
+    }
 }
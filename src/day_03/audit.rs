@@ -0,0 +1,209 @@
+use std::{error::Error, fmt};
+
+use super::{
+    group::{self, Grouping},
+    item_set::ItemSet,
+    priority::{Alphabet, ItemError},
+};
+
+/// What is unexpected about some lines of the puzzle input.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AnomalyKind {
+    /// The items can not be split into two compartements of equal size.
+    OddLength(usize),
+    /// No item is in both compartements of the rucksack.
+    NoMisplacedItem,
+    MultipleMisplacedItems(Vec<char>),
+    /// No item is in the rucksacks of all elves of the group.
+    NoBadge,
+    MultipleBadges(Vec<char>),
+    /// Lines at the end which are too few to form a group.
+    IncompleteGroup,
+}
+
+/// An anomaly of the lines (counted from 1) of the puzzle input.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Anomaly {
+    pub lines: Vec<usize>,
+    pub kind: AnomalyKind,
+}
+
+impl fmt::Display for Anomaly {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.lines.as_slice() {
+            [line] => write!(f, "line {line}: ")?,
+            [first, .., last] => write!(f, "lines {first}-{last}: ")?,
+            [] => {}
+        }
+
+        let items = |items: &[char]| {
+            items
+                .iter()
+                .map(char::to_string)
+                .collect::<Vec<_>>()
+                .join(", ")
+        };
+        match &self.kind {
+            AnomalyKind::OddLength(len) => write!(f, "odd number of items ({len})"),
+            AnomalyKind::NoMisplacedItem => write!(f, "no misplaced item"),
+            AnomalyKind::MultipleMisplacedItems(found) => {
+                write!(f, "multiple misplaced items ({})", items(found))
+            }
+            AnomalyKind::NoBadge => write!(f, "no badge"),
+            AnomalyKind::MultipleBadges(found) => write!(f, "multiple badges ({})", items(found)),
+            AnomalyKind::IncompleteGroup => write!(f, "incomplete group"),
+        }
+    }
+}
+
+/// Why the puzzle input can not be audited.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AuditError {
+    Item(ItemError),
+    /// Groups need at least one elf.
+    ZeroGroupSize,
+}
+
+impl fmt::Display for AuditError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AuditError::Item(error) => write!(f, "{error}"),
+            AuditError::ZeroGroupSize => write!(f, "groups need at least one elf"),
+        }
+    }
+}
+
+impl Error for AuditError {}
+
+impl From<ItemError> for AuditError {
+    fn from(error: ItemError) -> Self {
+        AuditError::Item(error)
+    }
+}
+
+/// Check the assumptions of the puzzle: every rucksack has exactly one misplaced item and every
+/// group of `group_size` elves exactly one badge.
+///
+/// The anomalies are ordered by their first line, rucksacks before the group they are part of.
+pub fn audit(inp: &str, group_size: usize) -> Result<Vec<Anomaly>, AuditError> {
+    Alphabet::standard().validate(inp)?;
+    let lines = inp.lines().collect::<Vec<_>>();
+    let groups =
+        group::group(&lines, &Grouping::Chunks(group_size)).ok_or(AuditError::ZeroGroupSize)?;
+    let mut anomalies = vec![];
+
+    for (i, group) in groups.iter().enumerate() {
+        let start = i * group_size;
+        audit_rucksacks(&mut anomalies, start, &lines[start..start + group_size]);
+
+        let badges = group.badges().expect("the input is valid");
+        let kind = match badges.len() {
+            0 => AnomalyKind::NoBadge,
+            1 => continue,
+            _ => AnomalyKind::MultipleBadges(badges.iter().collect()),
+        };
+        anomalies.push(Anomaly {
+            lines: (start + 1..=start + group_size).collect(),
+            kind,
+        });
+    }
+
+    // the lines after the last complete group
+    let start = groups.len() * group_size;
+    if start < lines.len() {
+        audit_rucksacks(&mut anomalies, start, &lines[start..]);
+        anomalies.push(Anomaly {
+            lines: (start + 1..=lines.len()).collect(),
+            kind: AnomalyKind::IncompleteGroup,
+        });
+    }
+    Ok(anomalies)
+}
+
+/// Audit the rucksacks of the given lines, the first of which has the index `start`.
+fn audit_rucksacks(anomalies: &mut Vec<Anomaly>, start: usize, lines: &[&str]) {
+    for (i, line) in lines.iter().enumerate() {
+        if let Some(kind) = audit_rucksack(line) {
+            anomalies.push(Anomaly {
+                lines: vec![start + i + 1],
+                kind,
+            });
+        }
+    }
+}
+
+fn audit_rucksack(line: &str) -> Option<AnomalyKind> {
+    if !line.len().is_multiple_of(2) {
+        return Some(AnomalyKind::OddLength(line.len()));
+    }
+    let (left, right) = line.split_at(line.len() / 2);
+    let misplaced = ItemSet::from_valid(left).intersection(ItemSet::from_valid(right));
+    match misplaced.len() {
+        0 => Some(AnomalyKind::NoMisplacedItem),
+        1 => None,
+        _ => Some(AnomalyKind::MultipleMisplacedItems(
+            misplaced.iter().collect(),
+        )),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_audit_example() {
+        let inp = "vJrwpWtwJgWrhcsFMMfFFhFp
+jqHRNqRjqzjGDLGLrsFMfFZSrLrFZsSL
+PmmdzqPrVvPwwTWBwg
+wMqvLMZHhHMvwLHjbvcjnnSBnvTQFn
+ttgJtRGJQctTZtZT
+CrZsJsPPZsGzwwsLwLmpwMDw";
+        assert_eq!(audit(inp, 3), Ok(vec![]));
+    }
+
+    #[test]
+    fn test_audit_anomalies() {
+        let inp = "abcabc\nabcd\nabcXd\nxyzx\nxyzY\nx\nab";
+        let anomaly = |lines: Vec<usize>, kind| Anomaly { lines, kind };
+        assert_eq!(
+            audit(inp, 2).unwrap(),
+            vec![
+                anomaly(
+                    vec![1],
+                    AnomalyKind::MultipleMisplacedItems(vec!['a', 'b', 'c'])
+                ),
+                anomaly(vec![2], AnomalyKind::NoMisplacedItem),
+                anomaly(vec![1, 2], AnomalyKind::MultipleBadges(vec!['a', 'b', 'c'])),
+                anomaly(vec![3], AnomalyKind::OddLength(5)),
+                anomaly(vec![3, 4], AnomalyKind::NoBadge),
+                anomaly(vec![5], AnomalyKind::NoMisplacedItem),
+                anomaly(vec![6], AnomalyKind::OddLength(1)),
+                anomaly(vec![7], AnomalyKind::NoMisplacedItem),
+                anomaly(vec![7], AnomalyKind::IncompleteGroup),
+            ]
+        );
+        assert_eq!(
+            audit("ab\na-", 3),
+            Err(AuditError::Item(ItemError { line: 2, item: '-' }))
+        );
+        assert_eq!(audit("ab", 0), Err(AuditError::ZeroGroupSize));
+    }
+
+    #[test]
+    fn test_display_anomaly() {
+        let anomalies = audit("abcabc\nabcd\nabab", 3).unwrap();
+        assert_eq!(
+            anomalies
+                .iter()
+                .map(ToString::to_string)
+                .collect::<Vec<_>>(),
+            vec![
+                "line 1: multiple misplaced items (a, b, c)",
+                "line 2: no misplaced item",
+                "line 3: multiple misplaced items (a, b)",
+                "lines 1-3: multiple badges (a, b)",
+            ]
+        );
+    }
+}
@@ -0,0 +1,139 @@
+use super::{
+    item_set::ItemSet,
+    priority::{Alphabet, AlphabetSet, InvalidItem},
+};
+
+/// A group of elves, holding the content of each of their rucksacks.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ElveGroup(pub Vec<String>);
+
+impl ElveGroup {
+    /// Set of the items which are in the rucksacks of all elves of the group.
+    pub fn badges(&self) -> Result<ItemSet, InvalidItem> {
+        let mut sets = self
+            .0
+            .iter()
+            .map(|rucksack| ItemSet::try_from(rucksack.as_str()));
+        match sets.next() {
+            Some(first) => sets.try_fold(first?, |common, set| Ok(common.intersection(set?))),
+            None => Ok(ItemSet::new()),
+        }
+    }
+
+    /// Set of the items which are in the rucksacks of all elves of the group, for rucksacks
+    /// packed with the items of the given alphabet.
+    pub fn badges_in<'a>(&self, alphabet: &'a Alphabet) -> Result<AlphabetSet<'a>, InvalidItem> {
+        let mut sets = self.0.iter().map(|rucksack| alphabet.set(rucksack));
+        match sets.next() {
+            Some(first) => sets.try_fold(first?, |common, set| {
+                Ok(common
+                    .intersection(set?)
+                    .expect("all sets are of the same alphabet"))
+            }),
+            None => alphabet.set(""),
+        }
+    }
+}
+
+/// How rucksacks are combined into groups.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Grouping {
+    /// Consecutive groups of the given size, a smaller group at the end is dropped.
+    Chunks(usize),
+    /// Every run of the given number of consecutive rucksacks, overlapping each other.
+    Windows(usize),
+    /// Groups of the rucksacks at the given indices.
+    Indices(Vec<Vec<usize>>),
+}
+
+/// Combine the rucksacks into groups. Returns `None` if an index is out of range or a size is 0.
+pub fn group(rucksacks: &[&str], grouping: &Grouping) -> Option<Vec<ElveGroup>> {
+    let to_group = |rucksacks: &[&str]| {
+        ElveGroup(
+            rucksacks
+                .iter()
+                .map(|rucksack| rucksack.to_string())
+                .collect(),
+        )
+    };
+
+    match grouping {
+        Grouping::Chunks(0) | Grouping::Windows(0) => None,
+        Grouping::Chunks(size) => Some(rucksacks.chunks_exact(*size).map(to_group).collect()),
+        Grouping::Windows(size) => Some(rucksacks.windows(*size).map(to_group).collect()),
+        Grouping::Indices(groups) => groups
+            .iter()
+            .map(|indices| {
+                let members = indices
+                    .iter()
+                    .map(|&index| rucksacks.get(index).map(|rucksack| rucksack.to_string()))
+                    .collect::<Option<Vec<_>>>()?;
+                Some(ElveGroup(members))
+            })
+            .collect(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const RUCKSACKS: [&str; 5] = ["abX", "bcX", "cdX", "bdY", "bY"];
+
+    #[test]
+    fn test_group_chunks() {
+        let groups = group(&RUCKSACKS, &Grouping::Chunks(2)).unwrap();
+        assert_eq!(groups.len(), 2);
+        assert_eq!(
+            groups[1],
+            ElveGroup(vec!["cdX".to_string(), "bdY".to_string()])
+        );
+        assert_eq!(groups[1].badges(), ItemSet::try_from("d"));
+        assert!(group(&RUCKSACKS, &Grouping::Chunks(0)).is_none());
+    }
+
+    #[test]
+    fn test_group_windows() {
+        let groups = group(&RUCKSACKS, &Grouping::Windows(3)).unwrap();
+        assert_eq!(
+            groups
+                .iter()
+                .map(|group| group.badges().unwrap())
+                .collect::<Vec<_>>(),
+            vec![
+                ItemSet::try_from("X").unwrap(),
+                ItemSet::new(),
+                ItemSet::new()
+            ]
+        );
+    }
+
+    #[test]
+    fn test_group_indices() {
+        let groups = group(
+            &RUCKSACKS,
+            &Grouping::Indices(vec![vec![0, 1, 3, 4], vec![2]]),
+        )
+        .unwrap();
+        assert_eq!(groups[0].badges(), ItemSet::try_from("b"));
+        assert_eq!(groups[1].badges(), ItemSet::try_from("cdX"));
+        assert!(group(&RUCKSACKS, &Grouping::Indices(vec![vec![5]])).is_none());
+        assert_eq!(ElveGroup(vec![]).badges(), Ok(ItemSet::new()));
+        assert_eq!(
+            ElveGroup(vec!["ab".to_string(), "a?".to_string()]).badges(),
+            Err(InvalidItem('?'))
+        );
+    }
+
+    #[test]
+    fn test_badges_in() {
+        let alphabet = Alphabet::new("🍎🍌🍒").unwrap();
+        let group = ElveGroup(vec!["🍎🍌".to_string(), "🍌🍒".to_string()]);
+        assert_eq!(group.badges_in(&alphabet).unwrap().items(), vec!['🍌']);
+        assert!(ElveGroup(vec![]).badges_in(&alphabet).unwrap().is_empty());
+        assert_eq!(
+            ElveGroup(vec!["🍎".to_string(), "a".to_string()]).badges_in(&alphabet),
+            Err(InvalidItem('a'))
+        );
+    }
+}
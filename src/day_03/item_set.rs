@@ -0,0 +1,136 @@
+use std::fmt;
+
+use super::priority::{InvalidItem, Priority};
+
+/// A set of items, stored as one bit per priority.
+#[derive(Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct ItemSet(u64);
+
+impl ItemSet {
+    pub fn new() -> ItemSet {
+        ItemSet(0)
+    }
+
+    pub(super) fn from_bits(bits: u64) -> ItemSet {
+        ItemSet(bits)
+    }
+
+    pub(super) fn bits(&self) -> u64 {
+        self.0
+    }
+
+    /// Collect items which have already been validated, see `TryFrom<&str>` for any others.
+    ///
+    /// Panics if an item is no letter.
+    pub(super) fn from_valid(items: &str) -> ItemSet {
+        ItemSet::try_from(items).unwrap_or_else(|error| panic!("{error}"))
+    }
+
+    /// Add an item, returning whether it was not yet in the set.
+    ///
+    /// Items which are no letter are rejected, see `Alphabet` for other items.
+    pub fn try_insert(&mut self, item: char) -> Result<bool, InvalidItem> {
+        let bit = 1 << item.to_prio()?;
+        let new = self.0 & bit == 0;
+        self.0 |= bit;
+        Ok(new)
+    }
+
+    pub fn contains(&self, item: char) -> bool {
+        item.to_prio().is_ok_and(|prio| self.0 & (1 << prio) != 0)
+    }
+
+    pub fn union(self, other: ItemSet) -> ItemSet {
+        ItemSet(self.0 | other.0)
+    }
+
+    pub fn intersection(self, other: ItemSet) -> ItemSet {
+        ItemSet(self.0 & other.0)
+    }
+
+    pub fn len(&self) -> usize {
+        self.0.count_ones() as usize
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0 == 0
+    }
+
+    /// Iterate over the items, ordered by their priority.
+    pub fn iter(&self) -> impl Iterator<Item = char> {
+        let bits = self.0;
+        (1..=52)
+            .filter(move |prio| bits & (1 << prio) != 0)
+            .map(|prio| match prio {
+                1..=26 => (b'a' + prio - 1) as char,
+                _ => (b'A' + prio - 27) as char,
+            })
+    }
+
+    /// Sum of the priorities of all items.
+    pub fn priority_sum(&self) -> u32 {
+        // the bits are the priorities
+        (1..=52).filter(|prio| self.0 & (1 << prio) != 0).sum()
+    }
+}
+
+impl TryFrom<&str> for ItemSet {
+    type Error = InvalidItem;
+
+    fn try_from(items: &str) -> Result<Self, Self::Error> {
+        let mut set = ItemSet::new();
+        for item in items.chars() {
+            set.try_insert(item)?;
+        }
+        Ok(set)
+    }
+}
+
+impl fmt::Debug for ItemSet {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_set().entries(self.iter()).finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_item_set() {
+        let mut set = ItemSet::new();
+        assert!(set.is_empty());
+        assert_eq!(set.try_insert('p'), Ok(true));
+        assert_eq!(set.try_insert('Z'), Ok(true));
+        assert_eq!(set.try_insert('p'), Ok(false));
+        assert_eq!(set.len(), 2);
+        assert!(set.contains('Z'));
+        assert!(!set.contains('z'));
+        assert!(!set.contains('1'));
+        assert_eq!(set.iter().collect::<Vec<_>>(), vec!['p', 'Z']);
+        assert_eq!(set.priority_sum(), 16 + 52);
+    }
+
+    #[test]
+    fn test_item_set_operations() {
+        let left = ItemSet::try_from("abcA").unwrap();
+        let right = ItemSet::try_from("cdA").unwrap();
+        assert_eq!(left.intersection(right), ItemSet::try_from("Ac").unwrap());
+        assert_eq!(left.union(right).len(), 5);
+        assert_eq!(format!("{:?}", left.intersection(right)), "{'c', 'A'}");
+    }
+
+    #[test]
+    fn test_item_set_invalid_item() {
+        let mut set = ItemSet::try_from("ab").unwrap();
+        assert_eq!(set.try_insert('!'), Err(InvalidItem('!')));
+        assert_eq!(ItemSet::try_from("a1b"), Err(InvalidItem('1')));
+        assert_eq!(set, ItemSet::try_from("ba").unwrap());
+    }
+
+    #[test]
+    #[should_panic(expected = "'!' is no item")]
+    fn test_item_set_from_valid() {
+        ItemSet::from_valid("a!");
+    }
+}
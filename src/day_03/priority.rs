@@ -0,0 +1,269 @@
+use std::{collections::HashMap, error::Error, fmt, ptr};
+
+use super::item_set::ItemSet;
+
+/// Largest number of items in an alphabet, so every item has a bit of an `ItemSet`.
+pub const MAX_ITEMS: usize = 63;
+
+pub trait Priority {
+    /// Get the priority of this item for the elves.
+    fn to_prio(self) -> Result<u32, InvalidItem>;
+}
+
+/// Priorities of the puzzle: `a` to `z` are 1 to 26 and `A` to `Z` are 27 to 52.
+impl Priority for char {
+    fn to_prio(self) -> Result<u32, InvalidItem> {
+        match self {
+            'a'..='z' => Ok(self as u32 - 'a' as u32 + 1),
+            'A'..='Z' => Ok(self as u32 - 'A' as u32 + 27),
+            _ => Err(InvalidItem(self)),
+        }
+    }
+}
+
+/// A character which is not part of the alphabet.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct InvalidItem(pub char);
+
+impl fmt::Display for InvalidItem {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "'{}' is no item", self.0)
+    }
+}
+
+impl Error for InvalidItem {}
+
+/// An invalid item in a line (counted from 1) of the puzzle input.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ItemError {
+    pub line: usize,
+    pub item: char,
+}
+
+impl fmt::Display for ItemError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "line {}: '{}' is no item", self.line, self.item)
+    }
+}
+
+impl Error for ItemError {}
+
+/// Error while building an alphabet.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AlphabetError {
+    /// More than `MAX_ITEMS` items.
+    TooManyItems(usize),
+    DuplicateItem(char),
+}
+
+impl fmt::Display for AlphabetError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AlphabetError::TooManyItems(count) => {
+                write!(
+                    f,
+                    "{count} items are too many, at most {MAX_ITEMS} are supported"
+                )
+            }
+            AlphabetError::DuplicateItem(item) => write!(f, "'{item}' is listed twice"),
+        }
+    }
+}
+
+impl Error for AlphabetError {}
+
+/// The items which may be packed and their priorities, any Unicode characters are allowed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Alphabet {
+    /// Items in the order they were given, the bit of an item in an `ItemSet` is its position
+    /// counted from 1.
+    items: Vec<(char, u32)>,
+    bits: HashMap<char, u32>,
+}
+
+impl Alphabet {
+    /// The alphabet of the puzzle, whose bits are the priorities of the items.
+    pub fn standard() -> Alphabet {
+        Alphabet::new(&('a'..='z').chain('A'..='Z').collect::<String>())
+            .expect("the standard alphabet is valid")
+    }
+
+    /// Create an alphabet whose items have the priority of their position, counted from 1.
+    pub fn new(items: &str) -> Result<Alphabet, AlphabetError> {
+        Alphabet::from_table(items.chars().zip(1..))
+    }
+
+    /// Create an alphabet with explicit priorities.
+    pub fn from_table(
+        table: impl IntoIterator<Item = (char, u32)>,
+    ) -> Result<Alphabet, AlphabetError> {
+        let items = table.into_iter().collect::<Vec<_>>();
+        if items.len() > MAX_ITEMS {
+            return Err(AlphabetError::TooManyItems(items.len()));
+        }
+
+        let mut bits = HashMap::new();
+        for (bit, &(item, _)) in (1..).zip(&items) {
+            if bits.insert(item, bit).is_some() {
+                return Err(AlphabetError::DuplicateItem(item));
+            }
+        }
+        Ok(Alphabet { items, bits })
+    }
+
+    pub fn priority(&self, item: char) -> Result<u32, InvalidItem> {
+        let bit = self.bits.get(&item).ok_or(InvalidItem(item))?;
+        Ok(self.items[*bit as usize - 1].1)
+    }
+
+    /// Collect the given items into a set of this alphabet.
+    pub fn set(&self, items: &str) -> Result<AlphabetSet<'_>, InvalidItem> {
+        let mut bits = 0;
+        for item in items.chars() {
+            bits |= 1 << self.bits.get(&item).ok_or(InvalidItem(item))?;
+        }
+        Ok(AlphabetSet {
+            alphabet: self,
+            set: ItemSet::from_bits(bits),
+        })
+    }
+
+    /// Check that all lines only contain items, returning the first invalid one.
+    pub fn validate(&self, inp: &str) -> Result<(), ItemError> {
+        for (i, line) in inp.lines().enumerate() {
+            if let Some(item) = line.chars().find(|item| !self.bits.contains_key(item)) {
+                return Err(ItemError { line: i + 1, item });
+            }
+        }
+        Ok(())
+    }
+}
+
+/// A set of items of an alphabet.
+///
+/// The bits of the set are the positions of the items in their alphabet, so the set is only
+/// meaningful together with it, unlike an `ItemSet` of letters.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AlphabetSet<'a> {
+    alphabet: &'a Alphabet,
+    set: ItemSet,
+}
+
+impl<'a> AlphabetSet<'a> {
+    /// Items which are in both sets, `None` if the sets belong to different alphabets.
+    pub fn intersection(self, other: AlphabetSet<'a>) -> Option<AlphabetSet<'a>> {
+        let same = ptr::eq(self.alphabet, other.alphabet) || self.alphabet == other.alphabet;
+        same.then(|| AlphabetSet {
+            alphabet: self.alphabet,
+            set: self.set.intersection(other.set),
+        })
+    }
+
+    pub fn len(&self) -> usize {
+        self.set.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.set.is_empty()
+    }
+
+    /// Get the items of the set, in the order of the alphabet.
+    pub fn items(&self) -> Vec<char> {
+        self.entries().map(|&(item, _)| item).collect()
+    }
+
+    /// Sum of the priorities of all items of the set.
+    pub fn priority_sum(&self) -> u32 {
+        self.entries().map(|&(_, priority)| priority).sum()
+    }
+
+    fn entries(&self) -> impl Iterator<Item = &'a (char, u32)> {
+        let bits = self.set.bits();
+        (1..)
+            .zip(&self.alphabet.items)
+            .filter(move |&(bit, _)| bits & (1 << bit) != 0)
+            .map(|(_, entry)| entry)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_to_prio() {
+        assert_eq!('a'.to_prio(), Ok(1));
+        assert_eq!('Z'.to_prio(), Ok(52));
+        assert_eq!('1'.to_prio(), Err(InvalidItem('1')));
+        assert_eq!('ä'.to_prio(), Err(InvalidItem('ä')));
+        assert_eq!('['.to_prio(), Err(InvalidItem('[')));
+    }
+
+    #[test]
+    fn test_standard_alphabet() {
+        let alphabet = Alphabet::standard();
+        for item in ('a'..='z').chain('A'..='Z') {
+            assert_eq!(alphabet.priority(item), item.to_prio());
+        }
+        let set = alphabet.set("pLp").unwrap();
+        let letters = ItemSet::try_from("Lp").unwrap();
+        assert_eq!(set.items(), letters.iter().collect::<Vec<_>>());
+        assert_eq!(set.priority_sum(), letters.priority_sum());
+    }
+
+    #[test]
+    fn test_unicode_alphabet() {
+        let alphabet = Alphabet::from_table([('🍎', 10), ('🍌', 1), ('ß', 5)]).unwrap();
+        assert_eq!(alphabet.priority('🍎'), Ok(10));
+        assert_eq!(alphabet.priority('a'), Err(InvalidItem('a')));
+
+        let common = alphabet
+            .set("🍎ß🍌")
+            .unwrap()
+            .intersection(alphabet.set("ß🍎").unwrap())
+            .unwrap();
+        assert_eq!(common.items(), vec!['🍎', 'ß']);
+        assert_eq!(common.priority_sum(), 15);
+        assert_eq!(common.len(), 2);
+        assert_eq!(alphabet.set("🍎x"), Err(InvalidItem('x')));
+    }
+
+    #[test]
+    fn test_intersection_of_different_alphabets() {
+        let fruits = Alphabet::new("🍎🍌").unwrap();
+        let standard = Alphabet::standard();
+        let fruit = fruits.set("🍎").unwrap();
+        assert_eq!(fruit.intersection(standard.set("a").unwrap()), None);
+        // an equal alphabet is the same one
+        let copy = fruits.clone();
+        assert_eq!(
+            fruit
+                .intersection(copy.set("🍎🍌").unwrap())
+                .unwrap()
+                .items(),
+            vec!['🍎']
+        );
+    }
+
+    #[test]
+    fn test_alphabet_errors() {
+        assert_eq!(
+            Alphabet::new("abca"),
+            Err(AlphabetError::DuplicateItem('a'))
+        );
+        assert_eq!(
+            Alphabet::new(&"x".repeat(64)),
+            Err(AlphabetError::TooManyItems(64))
+        );
+    }
+
+    #[test]
+    fn test_validate() {
+        let alphabet = Alphabet::standard();
+        assert_eq!(alphabet.validate("abc\nABC"), Ok(()));
+        assert_eq!(
+            alphabet.validate("abc\nAB1C").unwrap_err().to_string(),
+            "line 2: '1' is no item"
+        );
+    }
+}
@@ -0,0 +1,183 @@
+use std::{collections::BTreeMap, fmt};
+
+use super::priority::{Alphabet, ItemError};
+
+/// How to sort a rucksack so that no item type is in both compartements.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Plan {
+    /// Item types in the left compartement afterwards.
+    pub left: Vec<char>,
+    /// Item types in the right compartement afterwards.
+    pub right: Vec<char>,
+    /// Items exchanged between the compartements, as (from left, from right).
+    pub swaps: Vec<(char, char)>,
+}
+
+impl Plan {
+    /// Number of items which change their compartement.
+    pub fn moves(&self) -> usize {
+        2 * self.swaps.len()
+    }
+}
+
+/// Find the fewest swaps which sort the items of a rucksack by type into its compartements.
+///
+/// Every item type has to end up in one compartement while both keep their size, which is a
+/// subset sum over the types. Moving items in only one direction would change the sizes, so
+/// every move is part of a swap. Returns `None` if the line has an odd length or no choice of
+/// types fills exactly one compartement.
+pub fn plan(rucksack: &str) -> Option<Plan> {
+    let items = rucksack.chars().collect::<Vec<_>>();
+    if !items.len().is_multiple_of(2) {
+        return None;
+    }
+    let (left, right) = items.split_at(items.len() / 2);
+
+    // counts of every type in the left and right compartement
+    let mut counts = BTreeMap::<char, (usize, usize)>::new();
+    for &item in left {
+        counts.entry(item).or_default().0 += 1;
+    }
+    for &item in right {
+        counts.entry(item).or_default().1 += 1;
+    }
+    let counts = counts.into_iter().collect::<Vec<_>>();
+
+    // costs[i][size]: fewest items to move for the first i types, filling `size` items of the
+    // left compartement with them
+    let size = left.len();
+    let mut costs = vec![vec![None; size + 1]; counts.len() + 1];
+    costs[0][0] = Some(0);
+    for (i, &(_, (l, r))) in counts.iter().enumerate() {
+        for filled in 0..=size {
+            // keep the type right, moving its left items over
+            let right = costs[i][filled].map(|cost| cost + l);
+            // keep the type left, moving its right items over
+            let left = filled
+                .checked_sub(l + r)
+                .and_then(|before| costs[i][before])
+                .map(|cost| cost + r);
+            costs[i + 1][filled] = match (left, right) {
+                (Some(left), Some(right)) => Some(left.min(right)),
+                (left, right) => left.or(right),
+            };
+        }
+    }
+    costs[counts.len()][size]?;
+
+    let mut keep_left = vec![false; counts.len()];
+    let mut filled = size;
+    for (i, &(_, (l, r))) in counts.iter().enumerate().rev() {
+        let cost = costs[i + 1][filled];
+        if costs[i][filled].map(|before| before + l) != cost {
+            keep_left[i] = true;
+            filled -= l + r;
+        }
+    }
+
+    let types = |left: bool| {
+        counts
+            .iter()
+            .zip(&keep_left)
+            .filter(move |&(_, &keep)| keep == left)
+            .map(|(&(item, _), _)| item)
+    };
+    let outgoing = left.iter().filter(|item| !types(true).any(|t| t == **item));
+    let incoming = right.iter().filter(|item| types(true).any(|t| t == **item));
+    Some(Plan {
+        left: types(true).collect(),
+        right: types(false).collect(),
+        swaps: outgoing.copied().zip(incoming.copied()).collect(),
+    })
+}
+
+/// Plans for all rucksacks of the puzzle input, by line (counted from 1).
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Reorganization {
+    pub plans: Vec<(usize, Plan)>,
+    /// Lines of the rucksacks which can not be sorted.
+    pub infeasible: Vec<usize>,
+}
+
+impl Reorganization {
+    /// Number of items which change their compartement in all rucksacks.
+    pub fn total_moves(&self) -> usize {
+        self.plans.iter().map(|(_, plan)| plan.moves()).sum()
+    }
+}
+
+impl fmt::Display for Reorganization {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (line, plan) in &self.plans {
+            if plan.swaps.is_empty() {
+                continue;
+            }
+            let swaps = plan
+                .swaps
+                .iter()
+                .map(|(left, right)| format!("{left}<->{right}"))
+                .collect::<Vec<_>>();
+            writeln!(f, "line {line}: swap {}", swaps.join(", "))?;
+        }
+        for line in &self.infeasible {
+            writeln!(f, "line {line}: infeasible")?;
+        }
+        writeln!(f, "total moves: {}", self.total_moves())
+    }
+}
+
+/// Plan the reorganization of every rucksack of the puzzle input.
+pub fn reorganize(inp: &str) -> Result<Reorganization, ItemError> {
+    Alphabet::standard().validate(inp)?;
+    let mut reorganization = Reorganization::default();
+    for (i, line) in inp.lines().enumerate() {
+        match plan(line) {
+            Some(plan) => reorganization.plans.push((i + 1, plan)),
+            None => reorganization.infeasible.push(i + 1),
+        }
+    }
+    Ok(reorganization)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_plan() {
+        // p is in both compartements, so its right item is swapped with the single v
+        let plan = plan("vJrwpWtwJgWrhcsFMMfFFhFp").unwrap();
+        assert_eq!(plan.swaps, vec![('v', 'p')]);
+        assert_eq!(plan.left, vec!['J', 'W', 'g', 'p', 'r', 't', 'w']);
+        assert!(plan.right.contains(&'v'));
+
+        assert_eq!(
+            super::plan("abab").unwrap(),
+            Plan {
+                left: vec!['a'],
+                right: vec!['b'],
+                swaps: vec![('b', 'a')],
+            }
+        );
+        assert_eq!(super::plan("").unwrap().moves(), 0);
+    }
+
+    #[test]
+    fn test_plan_infeasible() {
+        assert_eq!(plan("abc"), None);
+        // three a can not fill a compartement of two
+        assert_eq!(plan("aaab"), None);
+    }
+
+    #[test]
+    fn test_reorganize() {
+        let reorganization = reorganize("abab\naaab\nabcd\naabbab").unwrap();
+        assert_eq!(reorganization.infeasible, vec![2]);
+        assert_eq!(reorganization.total_moves(), 4);
+        assert_eq!(
+            reorganization.to_string(),
+            "line 1: swap b<->a\nline 4: swap b<->a\nline 2: infeasible\ntotal moves: 4\n"
+        );
+        assert_eq!(reorganize("ab\nA1"), Err(ItemError { line: 2, item: '1' }));
+    }
+}
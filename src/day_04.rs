@@ -1,30 +1,20 @@
 use aoc_runner_derive::{aoc, aoc_generator};
 
-/// Struct representing a section for elves to clean.
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
-struct Section(usize, usize);
-
-impl Section {
-    fn from_string(inp: &str) -> Section {
-        let Some((left, right)) = inp.split_once("-") else {
-            panic!("Malformed input");
-        };
-
-        Section(
-            left.parse::<usize>().expect("Input is no real number"),
-            right.parse::<usize>().expect("Input is no real number"),
-        )
-    }
+use crate::{
+    interval::Interval,
+    solver::{Part, Run},
+};
 
-    /// Check, whether this section contains another specified section.
-    fn contains_section(&self, other: &Section) -> bool {
-        self.0 <= other.0 && self.1 >= other.1
-    }
+pub mod coverage;
 
-    /// Check, whether this section overlaps with another specified section.
-    fn overlaps_with(&self, other: &Section) -> bool {
-        self.1 >= other.0 && self.0 <= other.1
-    }
+use coverage::Coverage;
+
+/// Range of section IDs for an elf to clean.
+type Section = Interval;
+
+fn parse_section(inp: &str) -> Section {
+    inp.parse()
+        .unwrap_or_else(|error| panic!("Malformed input: {error}"))
 }
 
 /// Parse input into pairs of sections.
@@ -33,9 +23,9 @@ fn generator_day04(inp: &str) -> Vec<(Section, Section)> {
     inp.lines()
         .map(|line| {
             let Some((left, right)) = line.split_once(",") else {
-            panic!("Malformed input (no ',' present)");
-        };
-            (Section::from_string(left), Section::from_string(right))
+                panic!("Malformed input (no ',' present)");
+            };
+            (parse_section(left), parse_section(right))
         })
         .collect()
 }
@@ -43,7 +33,7 @@ fn generator_day04(inp: &str) -> Vec<(Section, Section)> {
 #[aoc(day4, part1)]
 fn day04_part1(sections: &[(Section, Section)]) -> usize {
     sections.iter().fold(0, |acc, (left, right)| {
-        acc + if left.contains_section(&right) || right.contains_section(&left) {
+        acc + if left.contains_interval(right) || right.contains_interval(left) {
             1
         } else {
             0
@@ -54,10 +44,27 @@ fn day04_part1(sections: &[(Section, Section)]) -> usize {
 #[aoc(day4, part2)]
 fn day04_part2(sections: &[(Section, Section)]) -> usize {
     sections.iter().fold(0, |acc, (left, right)| {
-        acc + if left.overlaps_with(&right) { 1 } else { 0 }
+        acc + if left.overlaps(right) { 1 } else { 0 }
     })
 }
 
+/// Analyze the sections of all elves of the puzzle input together.
+pub fn analyze_coverage(inp: &str) -> Coverage {
+    let sections = generator_day04(inp)
+        .into_iter()
+        .flat_map(|(left, right)| [left, right])
+        .collect::<Vec<_>>();
+    coverage::analyze(&sections)
+}
+
+/// Run the given part of this day for the provided input.
+pub(crate) fn run(part: Part, input: &str) -> Run {
+    match part {
+        Part::One => Run::measure(|| generator_day04(input), |sections| day04_part1(sections)),
+        Part::Two => Run::measure(|| generator_day04(input), |sections| day04_part2(sections)),
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -70,20 +77,20 @@ mod tests {
 2-6,4-8";
 
     #[test]
-    fn test_section_from_string() {
-        assert_eq!(Section::from_string("1-2"), Section(1, 2));
+    fn test_parse_section() {
+        assert_eq!(parse_section("1-2"), Section::new(1, 2));
     }
 
     #[test]
     #[should_panic]
-    fn test_section_from_string_no_dash() {
-        Section::from_string("123");
+    fn test_parse_section_no_dash() {
+        parse_section("123");
     }
 
     #[test]
     #[should_panic]
-    fn test_section_from_string_no_number() {
-        Section::from_string("123-abc");
+    fn test_parse_section_no_number() {
+        parse_section("123-abc");
     }
 
     #[test]
@@ -91,20 +98,20 @@ mod tests {
         assert_eq!(
             generator_day04(INPUT),
             vec![
-                (Section(2, 4), Section(6, 8)),
-                (Section(2, 3), Section(4, 5)),
-                (Section(5, 7), Section(7, 9)),
-                (Section(2, 8), Section(3, 7)),
-                (Section(6, 6), Section(4, 6)),
-                (Section(2, 6), Section(4, 8))
+                (Section::new(2, 4), Section::new(6, 8)),
+                (Section::new(2, 3), Section::new(4, 5)),
+                (Section::new(5, 7), Section::new(7, 9)),
+                (Section::new(2, 8), Section::new(3, 7)),
+                (Section::new(6, 6), Section::new(4, 6)),
+                (Section::new(2, 6), Section::new(4, 8))
             ]
         );
     }
 
     #[test]
-    fn test_section_contains_section() {
-        assert!(Section(2, 8).contains_section(&Section(3, 5)));
-        assert!(!Section(3, 6).contains_section(&Section(3, 7)));
+    fn test_section_contains_interval() {
+        assert!(Section::new(2, 8).contains_interval(&Section::new(3, 5)));
+        assert!(!Section::new(3, 6).contains_interval(&Section::new(3, 7)));
     }
 
     #[test]
@@ -114,13 +121,13 @@ mod tests {
     }
 
     #[test]
-    fn test_section_overlaps_with() {
-        assert!(Section(1, 4).overlaps_with(&Section(2, 5)));
-        assert!(Section(2, 5).overlaps_with(&Section(1, 4)));
-        assert!(Section(1, 3).overlaps_with(&Section(1, 3)));
-        assert!(Section(1, 5).overlaps_with(&Section(2, 4)));
-        assert!(Section(2, 4).overlaps_with(&Section(1, 5)));
-        assert!(!Section(1, 3).overlaps_with(&Section(4, 5)));
+    fn test_section_overlaps() {
+        assert!(Section::new(1, 4).overlaps(&Section::new(2, 5)));
+        assert!(Section::new(2, 5).overlaps(&Section::new(1, 4)));
+        assert!(Section::new(1, 3).overlaps(&Section::new(1, 3)));
+        assert!(Section::new(1, 5).overlaps(&Section::new(2, 4)));
+        assert!(Section::new(2, 4).overlaps(&Section::new(1, 5)));
+        assert!(!Section::new(1, 3).overlaps(&Section::new(4, 5)));
     }
 
     #[test]
@@ -128,4 +135,17 @@ mod tests {
         let sections = generator_day04(INPUT);
         assert_eq!(day04_part2(&sections), 4);
     }
+
+    #[test]
+    fn test_analyze_coverage() {
+        let coverage = analyze_coverage(INPUT);
+        assert_eq!(coverage.overlapping_pairs, 49);
+        // all sections but 2-4, 2-3, 4-5 and 7-9 contain 6
+        assert_eq!(coverage.max_coverage, 8);
+        assert_eq!(
+            coverage.most_covered.iter().collect::<Vec<_>>(),
+            vec![Section::new(6, 6)]
+        );
+        assert!(coverage.uncovered.is_empty());
+    }
 }
@@ -0,0 +1,168 @@
+use std::fmt;
+
+use crate::interval::{Interval, IntervalSet};
+
+/// How the sections of all elves cover the section IDs.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Coverage {
+    /// Number of pairs of sections sharing at least one ID, across all lines.
+    pub overlapping_pairs: usize,
+    /// Largest number of sections containing the same ID.
+    pub max_coverage: usize,
+    /// IDs which are in `max_coverage` sections.
+    pub most_covered: IntervalSet,
+    /// IDs between the smallest and largest covered one which are in no section.
+    pub uncovered: IntervalSet,
+}
+
+impl fmt::Display for Coverage {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let ids = |set: &IntervalSet| {
+            let intervals = set.iter().map(|i| i.to_string()).collect::<Vec<_>>();
+            if intervals.is_empty() {
+                "none".to_string()
+            } else {
+                intervals.join(", ")
+            }
+        };
+        writeln!(f, "overlapping pairs: {}", self.overlapping_pairs)?;
+        writeln!(f, "max coverage: {}", self.max_coverage)?;
+        writeln!(f, "most covered: {}", ids(&self.most_covered))?;
+        writeln!(f, "uncovered: {}", ids(&self.uncovered))
+    }
+}
+
+/// Analyze all sections together in O(n log n).
+pub fn analyze(sections: &[Interval]) -> Coverage {
+    if sections.is_empty() {
+        return Coverage::default();
+    }
+
+    // two sections are disjoint if one starts after the other one ends, which is counted by a
+    // binary search over the sorted starts
+    let mut starts = sections.iter().map(Interval::start).collect::<Vec<_>>();
+    starts.sort_unstable();
+    let disjoint = sections
+        .iter()
+        .map(|section| starts.len() - starts.partition_point(|&start| start <= section.end()))
+        .sum::<usize>();
+    let pairs = sections.len() * (sections.len() - 1) / 2;
+
+    // sweep over the changes of the coverage, a section leaves after its end. That is past
+    // `usize::MAX` for a section ending there, so the IDs of the events are widened
+    let mut events = sections
+        .iter()
+        .flat_map(|section| {
+            [
+                (section.start() as u128, 1),
+                (section.end() as u128 + 1, -1),
+            ]
+        })
+        .collect::<Vec<(u128, i64)>>();
+    events.sort_unstable();
+
+    let mut coverage = Coverage {
+        overlapping_pairs: pairs - disjoint,
+        ..Coverage::default()
+    };
+    let mut covering = 0;
+    for (i, &(id, change)) in events.iter().enumerate() {
+        covering += change;
+        let Some(&(next, _)) = events.get(i + 1) else {
+            break;
+        };
+        if next == id {
+            // not all changes at this ID are applied yet
+            continue;
+        }
+
+        // both are at most `usize::MAX`, since `id` is smaller than the ID of a later event
+        let ids = Interval::new(id as usize, (next - 1) as usize);
+        let covering = covering as usize;
+        if covering == 0 {
+            coverage.uncovered.insert(ids);
+        } else if covering > coverage.max_coverage {
+            coverage.max_coverage = covering;
+            coverage.most_covered = IntervalSet::from_iter([ids]);
+        } else if covering == coverage.max_coverage {
+            coverage.most_covered.insert(ids);
+        }
+    }
+    coverage
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sections(input: &[(usize, usize)]) -> Vec<Interval> {
+        input
+            .iter()
+            .map(|&(start, end)| Interval::new(start, end))
+            .collect()
+    }
+
+    #[test]
+    fn test_analyze() {
+        let coverage = analyze(&sections(&[(2, 4), (6, 8), (7, 9), (3, 3), (12, 12)]));
+        assert_eq!(coverage.overlapping_pairs, 2);
+        assert_eq!(coverage.max_coverage, 2);
+        assert_eq!(
+            coverage.most_covered.iter().collect::<Vec<_>>(),
+            sections(&[(3, 3), (7, 8)])
+        );
+        assert_eq!(
+            coverage.uncovered.iter().collect::<Vec<_>>(),
+            sections(&[(5, 5), (10, 11)])
+        );
+        assert_eq!(
+            coverage.to_string(),
+            "overlapping pairs: 2\nmax coverage: 2\nmost covered: 3-3, 7-8\nuncovered: 5-5, 10-11\n"
+        );
+        assert_eq!(analyze(&[]), Coverage::default());
+    }
+
+    #[test]
+    fn test_analyze_up_to_max_id() {
+        let max = usize::MAX;
+        let coverage = analyze(&sections(&[(max - 2, max), (max, max), (max - 5, max - 4)]));
+        assert_eq!(coverage.overlapping_pairs, 1);
+        assert_eq!(coverage.max_coverage, 2);
+        assert_eq!(
+            coverage.most_covered.iter().collect::<Vec<_>>(),
+            sections(&[(max, max)])
+        );
+        assert_eq!(
+            coverage.uncovered.iter().collect::<Vec<_>>(),
+            sections(&[(max - 3, max - 3)])
+        );
+    }
+
+    #[test]
+    fn test_analyze_matches_brute_force() {
+        // sections over a small range of IDs, a varying number of them starting at every ID, so
+        // many overlap and the coverage differs between the IDs
+        let sections = (1..=50)
+            .flat_map(|start| (0..=start % 7).map(move |len| Interval::new(start, start + len)))
+            .collect::<Vec<_>>();
+        let coverage = analyze(&sections);
+
+        let mut pairs = 0;
+        for (i, left) in sections.iter().enumerate() {
+            pairs += sections[i + 1..]
+                .iter()
+                .filter(|right| left.overlaps(right))
+                .count();
+        }
+        assert_eq!(coverage.overlapping_pairs, pairs);
+
+        let counts = (0..=60)
+            .map(|id| sections.iter().filter(|s| s.contains(id)).count())
+            .collect::<Vec<_>>();
+        let max = *counts.iter().max().unwrap();
+        assert_eq!(coverage.max_coverage, max);
+        for (id, &count) in counts.iter().enumerate() {
+            assert_eq!(coverage.most_covered.contains(id), count == max);
+        }
+    }
+}
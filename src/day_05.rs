@@ -2,6 +2,8 @@ use std::{error::Error, str::FromStr};
 
 use aoc_runner_derive::{aoc, aoc_generator};
 
+use crate::solver::{Part, Run};
+
 #[derive(Clone, Copy, Debug)]
 struct ParseError;
 
@@ -75,7 +77,10 @@ impl FromStr for Instruction {
         let destination = whitespaces.nth(1);
 
         let (Some(amount), Some(source), Some(destination)) = (amount, source, destination) else {
-            panic!("AoC betrayed us! ({:?} {:?} {:?})", amount, source, destination);
+            panic!(
+                "AoC betrayed us! ({:?} {:?} {:?})",
+                amount, source, destination
+            );
         };
 
         let amount = amount.parse::<usize>()?;
@@ -90,14 +95,7 @@ fn split_row_input_into_chunks(inp: &str) -> Vec<Option<Item>> {
     inp.chars()
         .collect::<Vec<char>>()
         .chunks(4)
-        .map(|chunk| {
-            chunk
-                .iter()
-                .collect::<String>()
-                .trim()
-                .parse::<Item>()
-                .map_or(None, |val| Some(val))
-        })
+        .map(|chunk| chunk.iter().collect::<String>().trim().parse::<Item>().ok())
         .collect::<Vec<_>>()
 }
 
@@ -171,13 +169,7 @@ fn day05_part2(input: &(Vec<Stack>, Vec<Instruction>)) -> String {
     // move crates around
     for Instruction(amount, source, target) in instructions {
         let items = stacks[source - 1].pop_n(amount);
-        stacks[target - 1].push_all(
-            items
-                .into_iter()
-                .filter(|item| item.is_some())
-                .map(|item| item.unwrap())
-                .collect(),
-        );
+        stacks[target - 1].push_all(items.into_iter().flatten().collect());
     }
 
     // combine top elements
@@ -192,6 +184,14 @@ fn day05_part2(input: &(Vec<Stack>, Vec<Instruction>)) -> String {
         })
 }
 
+/// Run the given part of this day for the provided input.
+pub(crate) fn run(part: Part, input: &str) -> Run {
+    match part {
+        Part::One => Run::measure(|| generator_day5(input), day05_part1),
+        Part::Two => Run::measure(|| generator_day5(input), day05_part2),
+    }
+}
+
 #[cfg(test)]
 mod tests {
 
@@ -235,6 +235,7 @@ move 1 from 1 to 2";
     }
 
     #[test]
+    #[allow(clippy::len_zero)]
     fn test_pop_n() {
         let mut stack = Stack {
             items: vec![Item('A'), Item('B')],
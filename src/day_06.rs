@@ -1,5 +1,7 @@
 use aoc_runner_derive::aoc;
 
+use crate::solver::{Part, Run};
+
 /// Find the position where N distinct characters occur in the string.
 fn find_first_n_distinct_characters<const N: usize>(inp: &str) -> usize {
     let mut current_window: [char; N] = [0 as char; N];
@@ -32,6 +34,14 @@ fn day06_part2(inp: &str) -> usize {
     find_first_n_distinct_characters::<14>(inp)
 }
 
+/// Run the given part of this day for the provided input.
+pub(crate) fn run(part: Part, input: &str) -> Run {
+    match part {
+        Part::One => Run::measure(|| input, |inp| day06_part1(inp)),
+        Part::Two => Run::measure(|| input, |inp| day06_part2(inp)),
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -0,0 +1,187 @@
+//! C-ABI for calling the solutions from other languages.
+//!
+//! The matching C header is generated into `OUT_DIR` when building the crate, and only copied
+//! to the checked in `include/aoc_2022.h` when building with `--features c-header`.
+
+use std::{
+    ffi::{c_char, CString},
+    panic::{self, AssertUnwindSafe},
+    ptr, slice,
+};
+
+use crate::solver::{self, Part, SolveError};
+
+/// Status returned by `aoc_solve`.
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AocStatus {
+    Ok = 0,
+    UnknownDay = 1,
+    UnknownPart = 2,
+    InvalidInput = 3,
+    NullPointer = 4,
+    Panicked = 5,
+}
+
+/// Get all days which can be solved.
+///
+/// The returned array is static and must not be freed. Its length is written to `len`.
+///
+/// # Safety
+///
+/// `len` must be null or point to writable memory for a `size_t`.
+#[no_mangle]
+pub unsafe extern "C" fn aoc_available_days(len: *mut usize) -> *const u8 {
+    let days = solver::available_days();
+    if !len.is_null() {
+        *len = days.len();
+    }
+    days.as_ptr()
+}
+
+/// Solve a part (`1` or `2`) of a day for the given input buffer.
+///
+/// On success, the answer is written to `answer` and `AOC_STATUS_OK` is returned. On any other
+/// status, `answer` receives a message describing the error instead. In both cases the string is
+/// owned by the caller and has to be released with `aoc_string_free`.
+///
+/// # Safety
+///
+/// `input` must point to `input_len` readable bytes (or be null if `input_len` is `0`) and
+/// `answer` must be null or point to writable memory for a `char *`.
+#[no_mangle]
+pub unsafe extern "C" fn aoc_solve(
+    day: u8,
+    part: u8,
+    input: *const u8,
+    input_len: usize,
+    answer: *mut *mut c_char,
+) -> AocStatus {
+    if answer.is_null() {
+        return AocStatus::NullPointer;
+    }
+    *answer = ptr::null_mut();
+
+    let (status, message) = match solve(day, part, input, input_len) {
+        Ok(result) => (AocStatus::Ok, result),
+        Err(error) => error,
+    };
+    *answer = into_c_string(message);
+    status
+}
+
+/// Free a string returned by this library. Passing null is a no-op.
+///
+/// # Safety
+///
+/// `string` must have been returned by this library and must not be used afterwards.
+#[no_mangle]
+pub unsafe extern "C" fn aoc_string_free(string: *mut c_char) {
+    if !string.is_null() {
+        drop(CString::from_raw(string));
+    }
+}
+
+/// Safe part of `aoc_solve`, mapping every failure to a status and a message.
+unsafe fn solve(
+    day: u8,
+    part: u8,
+    input: *const u8,
+    input_len: usize,
+) -> Result<String, (AocStatus, String)> {
+    let Some(part) = Part::from_number(part) else {
+        return Err((
+            AocStatus::UnknownPart,
+            SolveError::UnknownPart(part).to_string(),
+        ));
+    };
+
+    let input = if input_len == 0 {
+        &[]
+    } else if input.is_null() {
+        return Err((AocStatus::NullPointer, "input is null".to_string()));
+    } else {
+        slice::from_raw_parts(input, input_len)
+    };
+    let input = std::str::from_utf8(input).map_err(|error| {
+        (
+            AocStatus::InvalidInput,
+            format!("input is no UTF-8: {error}"),
+        )
+    })?;
+
+    // unwinding into foreign code is undefined behaviour, so panics on malformed input have to
+    // end here
+    match panic::catch_unwind(AssertUnwindSafe(|| solver::solve(day, part, input))) {
+        Ok(Ok(result)) => Ok(result),
+        Ok(Err(error)) => Err((AocStatus::UnknownDay, error.to_string())),
+        Err(payload) => {
+            let message = payload
+                .downcast_ref::<&str>()
+                .map(|message| message.to_string())
+                .or_else(|| payload.downcast_ref::<String>().cloned())
+                .unwrap_or_else(|| "solution panicked".to_string());
+            Err((AocStatus::Panicked, message))
+        }
+    }
+}
+
+/// Convert a string into an owned C string, dropping interior NUL bytes.
+fn into_c_string(string: String) -> *mut c_char {
+    let string = CString::new(string.replace('\0', "")).expect("NUL bytes have been removed");
+    string.into_raw()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::ffi::CStr;
+
+    use super::*;
+
+    unsafe fn call(day: u8, part: u8, input: &str) -> (AocStatus, String) {
+        let mut answer = ptr::null_mut();
+        let status = aoc_solve(day, part, input.as_ptr(), input.len(), &mut answer);
+        let result = CStr::from_ptr(answer).to_string_lossy().into_owned();
+        aoc_string_free(answer);
+        (status, result)
+    }
+
+    #[test]
+    fn test_aoc_available_days() {
+        let mut len = 0;
+        let days = unsafe { slice::from_raw_parts(aoc_available_days(&mut len), len) };
+        assert_eq!(days, &[1, 2, 3, 4, 5, 6]);
+    }
+
+    #[test]
+    fn test_aoc_solve() {
+        assert_eq!(
+            unsafe { call(2, 2, "A Y\nB X\nC Z") },
+            (AocStatus::Ok, "12".to_string())
+        );
+    }
+
+    #[test]
+    fn test_aoc_solve_errors() {
+        assert_eq!(unsafe { call(42, 1, "") }.0, AocStatus::UnknownDay);
+        assert_eq!(unsafe { call(1, 3, "") }.0, AocStatus::UnknownPart);
+        assert_eq!(unsafe { call(4, 1, "1-2") }.0, AocStatus::Panicked);
+    }
+
+    #[test]
+    fn test_aoc_solve_null_answer() {
+        assert_eq!(
+            unsafe { aoc_solve(1, 1, ptr::null(), 0, ptr::null_mut()) },
+            AocStatus::NullPointer
+        );
+    }
+
+    #[test]
+    fn test_header_is_up_to_date() {
+        assert_eq!(
+            include_str!("../include/aoc_2022.h"),
+            include_str!(concat!(env!("OUT_DIR"), "/aoc_2022.h")),
+            "run `cargo build --features c-header` to update include/aoc_2022.h"
+        );
+    }
+}
@@ -0,0 +1,332 @@
+//! Closed intervals of integers and sets of them.
+
+use std::{collections::BTreeMap, fmt, iter::FromIterator, str::FromStr};
+
+/// All integers from `start` to `end`, both included.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Interval {
+    start: usize,
+    end: usize,
+}
+
+impl Interval {
+    /// Create the interval from `start` to `end`. Panics if `start` is greater than `end`.
+    pub fn new(start: usize, end: usize) -> Interval {
+        assert!(start <= end, "interval {start}-{end} is reversed");
+        Interval { start, end }
+    }
+
+    pub fn start(&self) -> usize {
+        self.start
+    }
+
+    pub fn end(&self) -> usize {
+        self.end
+    }
+
+    /// Number of integers in the interval, which is one more than `usize::MAX` for the interval
+    /// of all of them.
+    pub fn length(&self) -> u128 {
+        (self.end - self.start) as u128 + 1
+    }
+
+    pub fn contains(&self, value: usize) -> bool {
+        self.start <= value && value <= self.end
+    }
+
+    /// Check, whether this interval contains all of another one.
+    pub fn contains_interval(&self, other: &Interval) -> bool {
+        self.start <= other.start && self.end >= other.end
+    }
+
+    /// Check, whether both intervals share at least one integer.
+    pub fn overlaps(&self, other: &Interval) -> bool {
+        self.end >= other.start && self.start <= other.end
+    }
+
+    /// Check, whether one interval ends directly before the other one starts.
+    pub fn is_adjacent(&self, other: &Interval) -> bool {
+        self.end.checked_add(1) == Some(other.start) || other.end.checked_add(1) == Some(self.start)
+    }
+
+    pub fn intersection(&self, other: &Interval) -> Option<Interval> {
+        self.overlaps(other)
+            .then(|| Interval::new(self.start.max(other.start), self.end.min(other.end)))
+    }
+
+    /// Combine both intervals, if they overlap or are adjacent.
+    pub fn union(&self, other: &Interval) -> Option<Interval> {
+        (self.overlaps(other) || self.is_adjacent(other))
+            .then(|| Interval::new(self.start.min(other.start), self.end.max(other.end)))
+    }
+
+    /// Parts of this interval which are not in the other one, ordered by their start.
+    pub fn difference(&self, other: &Interval) -> Vec<Interval> {
+        if !self.overlaps(other) {
+            return vec![*self];
+        }
+        let mut parts = vec![];
+        if self.start < other.start {
+            parts.push(Interval::new(self.start, other.start - 1));
+        }
+        if other.end < self.end {
+            parts.push(Interval::new(other.end + 1, self.end));
+        }
+        parts
+    }
+}
+
+impl fmt::Display for Interval {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}-{}", self.start, self.end)
+    }
+}
+
+impl FromStr for Interval {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (start, end) = s
+            .split_once('-')
+            .ok_or_else(|| format!("'{s}' is no interval (no '-' present)"))?;
+        let parse = |bound: &str| {
+            bound
+                .parse::<usize>()
+                .map_err(|_| format!("'{bound}' is no non-negative integer"))
+        };
+        let (start, end) = (parse(start)?, parse(end)?);
+        if start > end {
+            return Err(format!("interval '{s}' is reversed"));
+        }
+        Ok(Interval::new(start, end))
+    }
+}
+
+/// A set of integers, stored as disjoint intervals which are neither overlapping nor adjacent.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct IntervalSet {
+    /// End of every interval by its start.
+    intervals: BTreeMap<usize, usize>,
+}
+
+impl IntervalSet {
+    pub fn new() -> IntervalSet {
+        IntervalSet::default()
+    }
+
+    /// Add all integers of the interval, merging it with the ones it overlaps or touches.
+    pub fn insert(&mut self, interval: Interval) {
+        let mut merged = interval;
+        // an interval starting before can only touch the new one if it is the last of them
+        let before = self
+            .intervals
+            .range(..=interval.start)
+            .next_back()
+            .map(|(&start, &end)| Interval::new(start, end));
+        if let Some(union) = before.and_then(|before| before.union(&merged)) {
+            self.intervals.remove(&union.start);
+            merged = union;
+        }
+        let upper = merged.end.saturating_add(1);
+        let touched = self
+            .intervals
+            .range(merged.start..=upper)
+            .map(|(&start, &end)| Interval::new(start, end))
+            .collect::<Vec<_>>();
+        for other in touched {
+            self.intervals.remove(&other.start);
+            merged = merged
+                .union(&other)
+                .expect("touched intervals are joinable");
+        }
+        self.intervals.insert(merged.start, merged.end);
+    }
+
+    /// Remove all integers of the interval, splitting the intervals it cuts through.
+    pub fn remove(&mut self, interval: Interval) {
+        for other in self.query(interval) {
+            let (&start, &end) = self
+                .intervals
+                .range(..=other.start)
+                .next_back()
+                .expect("queried parts are in the set");
+            self.intervals.remove(&start);
+            for part in Interval::new(start, end).difference(&interval) {
+                self.intervals.insert(part.start, part.end);
+            }
+        }
+    }
+
+    pub fn contains(&self, value: usize) -> bool {
+        self.intervals
+            .range(..=value)
+            .next_back()
+            .is_some_and(|(_, &end)| value <= end)
+    }
+
+    /// Parts of the set which lie in the given interval, ordered by their start.
+    pub fn query(&self, interval: Interval) -> Vec<Interval> {
+        let first = self
+            .intervals
+            .range(..=interval.start)
+            .next_back()
+            .map_or(interval.start, |(&start, _)| start);
+        self.intervals
+            .range(first..=interval.end)
+            .filter_map(|(&start, &end)| Interval::new(start, end).intersection(&interval))
+            .collect()
+    }
+
+    /// All integers within the bounds which are not in the set.
+    pub fn complement(&self, bounds: Interval) -> IntervalSet {
+        let mut complement = IntervalSet::new();
+        complement.insert(bounds);
+        for interval in self.iter() {
+            complement.remove(interval);
+        }
+        complement
+    }
+
+    /// Iterate over the disjoint intervals, ordered by their start.
+    pub fn iter(&self) -> impl Iterator<Item = Interval> + '_ {
+        self.intervals
+            .iter()
+            .map(|(&start, &end)| Interval::new(start, end))
+    }
+
+    /// Number of integers in the set.
+    pub fn count(&self) -> u128 {
+        self.iter().map(|interval| interval.length()).sum()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.intervals.is_empty()
+    }
+}
+
+impl FromIterator<Interval> for IntervalSet {
+    fn from_iter<I: IntoIterator<Item = Interval>>(intervals: I) -> Self {
+        let mut set = IntervalSet::new();
+        for interval in intervals {
+            set.insert(interval);
+        }
+        set
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn intervals(set: &IntervalSet) -> Vec<(usize, usize)> {
+        set.iter()
+            .map(|interval| (interval.start(), interval.end()))
+            .collect()
+    }
+
+    #[test]
+    fn test_interval() {
+        let interval = Interval::new(3, 7);
+        assert_eq!(interval.length(), 5);
+        assert!(interval.contains(3) && interval.contains(7) && !interval.contains(8));
+        assert!(interval.contains_interval(&Interval::new(4, 7)));
+        assert!(!interval.contains_interval(&Interval::new(2, 4)));
+        assert!(interval.overlaps(&Interval::new(7, 9)));
+        assert!(!interval.overlaps(&Interval::new(8, 9)));
+        assert!(interval.is_adjacent(&Interval::new(8, 9)));
+        assert!(interval.is_adjacent(&Interval::new(0, 2)));
+        assert!(!interval.is_adjacent(&Interval::new(7, 9)));
+    }
+
+    #[test]
+    fn test_interval_full_range() {
+        let all = Interval::new(0, usize::MAX);
+        assert_eq!(all.length(), usize::MAX as u128 + 1);
+        assert_eq!(Interval::new(usize::MAX, usize::MAX).length(), 1);
+
+        let set = IntervalSet::from_iter([Interval::new(0, 9), Interval::new(10, usize::MAX)]);
+        assert_eq!(set.count(), usize::MAX as u128 + 1);
+        assert_eq!(intervals(&set), vec![(0, usize::MAX)]);
+    }
+
+    #[test]
+    fn test_interval_operations() {
+        let interval = Interval::new(3, 7);
+        assert_eq!(
+            interval.intersection(&Interval::new(5, 9)),
+            Some(Interval::new(5, 7))
+        );
+        assert_eq!(interval.intersection(&Interval::new(8, 9)), None);
+        assert_eq!(
+            interval.union(&Interval::new(8, 9)),
+            Some(Interval::new(3, 9))
+        );
+        assert_eq!(interval.union(&Interval::new(9, 9)), None);
+        assert_eq!(
+            interval.difference(&Interval::new(4, 5)),
+            vec![Interval::new(3, 3), Interval::new(6, 7)]
+        );
+        assert_eq!(interval.difference(&Interval::new(0, 9)), vec![]);
+        assert_eq!(interval.difference(&Interval::new(8, 9)), vec![interval]);
+    }
+
+    #[test]
+    fn test_interval_from_str() {
+        assert_eq!("2-4".parse(), Ok(Interval::new(2, 4)));
+        assert_eq!(Interval::new(2, 4).to_string(), "2-4");
+        assert!("24".parse::<Interval>().is_err());
+        assert_eq!(
+            "2-x".parse::<Interval>(),
+            Err("'x' is no non-negative integer".to_string())
+        );
+        assert!("-1-2".parse::<Interval>().is_err());
+        assert!("4-2".parse::<Interval>().is_err());
+    }
+
+    #[test]
+    #[should_panic(expected = "interval 4-2 is reversed")]
+    fn test_interval_reversed() {
+        Interval::new(4, 2);
+    }
+
+    #[test]
+    fn test_interval_set_insert() {
+        let mut set = [(1, 3), (10, 12), (6, 7)]
+            .into_iter()
+            .map(|(start, end)| Interval::new(start, end))
+            .collect::<IntervalSet>();
+        assert_eq!(intervals(&set), vec![(1, 3), (6, 7), (10, 12)]);
+
+        // adjacent on both sides
+        set.insert(Interval::new(4, 5));
+        assert_eq!(intervals(&set), vec![(1, 7), (10, 12)]);
+        set.insert(Interval::new(2, 11));
+        assert_eq!(intervals(&set), vec![(1, 12)]);
+        assert_eq!(set.count(), 12);
+        assert!(set.contains(12) && !set.contains(0) && !set.contains(13));
+    }
+
+    #[test]
+    fn test_interval_set_remove_and_query() {
+        let mut set = IntervalSet::from_iter([Interval::new(1, 10), Interval::new(15, 20)]);
+        set.remove(Interval::new(4, 16));
+        assert_eq!(intervals(&set), vec![(1, 3), (17, 20)]);
+        assert_eq!(
+            set.query(Interval::new(2, 18)),
+            vec![Interval::new(2, 3), Interval::new(17, 18)]
+        );
+        assert_eq!(set.query(Interval::new(5, 16)), vec![]);
+        set.remove(Interval::new(0, 30));
+        assert!(set.is_empty());
+    }
+
+    #[test]
+    fn test_interval_set_complement() {
+        let set = IntervalSet::from_iter([Interval::new(3, 4), Interval::new(8, 12)]);
+        assert_eq!(
+            intervals(&set.complement(Interval::new(1, 10))),
+            vec![(1, 2), (5, 7)]
+        );
+        assert!(set.complement(Interval::new(8, 12)).is_empty());
+    }
+}
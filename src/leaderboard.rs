@@ -0,0 +1,496 @@
+//! Analysis of the JSON export of a private leaderboard.
+
+use std::{
+    collections::{BTreeMap, HashMap},
+    fmt,
+    str::FromStr,
+};
+
+use serde::{Deserialize, Serialize};
+
+const SECONDS_PER_DAY: u64 = 24 * 60 * 60;
+
+/// A private leaderboard as exported by Advent of Code.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+pub struct Leaderboard {
+    pub event: String,
+    /// All members, keyed by their id.
+    pub members: HashMap<String, Member>,
+}
+
+/// A single member of a leaderboard.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+pub struct Member {
+    /// Anonymous users have no name.
+    pub name: Option<String>,
+    pub stars: u32,
+    pub local_score: u64,
+    /// Completion times of the stars of a member, keyed by day and part.
+    #[serde(default)]
+    completion_day_level: BTreeMap<u8, BTreeMap<u8, Star>>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+struct Star {
+    get_star_ts: u64,
+}
+
+/// Completion timestamps (seconds since the unix epoch) of both parts of a day.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct DayCompletion {
+    pub part1: Option<u64>,
+    pub part2: Option<u64>,
+}
+
+impl DayCompletion {
+    /// Seconds between finishing part 1 and part 2, if both are finished.
+    pub fn delta(&self) -> Option<u64> {
+        Some(self.part2?.saturating_sub(self.part1?))
+    }
+}
+
+impl Member {
+    /// Get the completion timestamps of all days this member has at least one star for.
+    pub fn completions(&self) -> BTreeMap<u8, DayCompletion> {
+        self.completion_day_level
+            .iter()
+            .map(|(&day, parts)| {
+                let completion = DayCompletion {
+                    part1: parts.get(&1).map(|star| star.get_star_ts),
+                    part2: parts.get(&2).map(|star| star.get_star_ts),
+                };
+                (day, completion)
+            })
+            .collect()
+    }
+
+    /// Timestamp of the most recent star of this member.
+    pub fn last_star(&self) -> Option<u64> {
+        self.completion_day_level
+            .values()
+            .flat_map(|parts| parts.values())
+            .map(|star| star.get_star_ts)
+            .max()
+    }
+}
+
+impl Leaderboard {
+    /// Parse a leaderboard from its JSON export.
+    pub fn from_json(json: &str) -> serde_json::Result<Leaderboard> {
+        serde_json::from_str(json)
+    }
+
+    /// Get the timestamp at which the puzzle of the given day was unlocked (midnight EST).
+    pub fn unlock_time(&self, day: u8) -> Option<u64> {
+        let year = self.event.parse::<i64>().ok()?;
+        let days = days_since_epoch(year, 12, day as i64);
+        // puzzles unlock at midnight in UTC-5
+        Some(days as u64 * SECONDS_PER_DAY + 5 * 60 * 60)
+    }
+
+    /// Rank all members according to the given scoring rule.
+    pub fn rank(&self, scoring: Scoring) -> Ranking {
+        let scores = match scoring {
+            Scoring::Local => {
+                let mut points = self.points_by(|completion| completion.part1);
+                for (id, part2) in self.points_by(|completion| completion.part2) {
+                    *points.entry(id).or_insert(0) += part2;
+                }
+                points
+            }
+            Scoring::Stars => self
+                .members
+                .iter()
+                .map(|(id, member)| (id.as_str(), member.stars as u64))
+                .collect(),
+            Scoring::Delta => self.points_by(DayCompletion::delta),
+        };
+
+        let mut rows = self
+            .members
+            .iter()
+            .map(|(id, member)| RankingRow {
+                rank: 0,
+                id: id.clone(),
+                name: display_name(id, member),
+                score: scores.get(id.as_str()).copied().unwrap_or(0),
+                stars: member.stars,
+                last_star: member.last_star(),
+            })
+            .collect::<Vec<_>>();
+        // just like on the website, ties are broken by whoever got their last star first
+        rows.sort_by(|a, b| {
+            b.score
+                .cmp(&a.score)
+                .then(
+                    a.last_star
+                        .unwrap_or(u64::MAX)
+                        .cmp(&b.last_star.unwrap_or(u64::MAX)),
+                )
+                .then(a.id.cmp(&b.id))
+        });
+        for (i, row) in rows.iter_mut().enumerate() {
+            row.rank = i + 1;
+        }
+
+        Ranking { scoring, rows }
+    }
+
+    /// Award points for every day by the given metric (lower is better): the best member gets as
+    /// many points as there are members, the second best one point less, and so on.
+    fn points_by(&self, metric: impl Fn(&DayCompletion) -> Option<u64>) -> HashMap<&str, u64> {
+        let mut results_by_day = BTreeMap::<u8, Vec<(u64, &str)>>::new();
+        for (id, member) in &self.members {
+            for (day, completion) in member.completions() {
+                if let Some(result) = metric(&completion) {
+                    results_by_day
+                        .entry(day)
+                        .or_default()
+                        .push((result, id.as_str()));
+                }
+            }
+        }
+
+        let mut points = HashMap::new();
+        for mut results in results_by_day.into_values() {
+            results.sort();
+            for (position, (_, id)) in results.into_iter().enumerate() {
+                *points.entry(id).or_insert(0) += (self.members.len() - position) as u64;
+            }
+        }
+        points
+    }
+}
+
+fn display_name(id: &str, member: &Member) -> String {
+    member
+        .name
+        .clone()
+        .unwrap_or_else(|| format!("(anonymous user #{id})"))
+}
+
+/// Number of days between the unix epoch and the given date.
+fn days_since_epoch(year: i64, month: i64, day: i64) -> i64 {
+    // see http://howardhinnant.github.io/date_algorithms.html#days_from_civil
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = year.div_euclid(400);
+    let year_of_era = year - era * 400;
+    let day_of_year = (153 * (month + if month > 2 { -3 } else { 9 }) + 2) / 5 + day - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+    era * 146097 + day_of_era - 719468
+}
+
+/// Rule to rank the members of a leaderboard by.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Scoring {
+    /// Points per star, depending on how many members got the star earlier (the official rule).
+    Local,
+    /// Number of stars.
+    Stars,
+    /// Points per day, depending on how many members solved part 2 faster after solving part 1.
+    Delta,
+}
+
+impl FromStr for Scoring {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "local" => Ok(Scoring::Local),
+            "stars" => Ok(Scoring::Stars),
+            "delta" => Ok(Scoring::Delta),
+            _ => Err(format!(
+                "unknown scoring '{s}' (expected local, stars or delta)"
+            )),
+        }
+    }
+}
+
+/// Members of a leaderboard, ordered by their score.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Ranking {
+    pub scoring: Scoring,
+    pub rows: Vec<RankingRow>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct RankingRow {
+    pub rank: usize,
+    pub id: String,
+    pub name: String,
+    pub score: u64,
+    pub stars: u32,
+    pub last_star: Option<u64>,
+}
+
+impl Ranking {
+    /// Convert this ranking into pretty-printed JSON.
+    pub fn to_json(&self) -> String {
+        serde_json::to_string_pretty(self).expect("rankings are always serializable")
+    }
+}
+
+impl fmt::Display for Ranking {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let width = self
+            .rows
+            .iter()
+            .map(|row| row.name.chars().count())
+            .max()
+            .unwrap_or(0)
+            .max(4);
+
+        writeln!(
+            f,
+            "{:>4}  {:<width$}  {:>6}  {:>5}",
+            "Rank", "Name", "Score", "Stars"
+        )?;
+        for row in &self.rows {
+            writeln!(
+                f,
+                "{:>4}  {:<width$}  {:>6}  {:>5}",
+                row.rank, row.name, row.score, row.stars
+            )?;
+        }
+        Ok(())
+    }
+}
+
+/// Stars of a member, with times relative to the unlock of each puzzle.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct MemberReport {
+    pub id: String,
+    pub name: String,
+    pub stars: u32,
+    pub days: Vec<DayReport>,
+}
+
+/// Times (in seconds) a member needed for the parts of a day.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct DayReport {
+    pub day: u8,
+    /// Seconds between the unlock of the puzzle and finishing part 1.
+    pub part1: Option<u64>,
+    /// Seconds between the unlock of the puzzle and finishing part 2.
+    pub part2: Option<u64>,
+    /// Seconds between finishing part 1 and part 2.
+    pub delta: Option<u64>,
+}
+
+impl Leaderboard {
+    /// Get the completion times of every member, in the order of the given ranking.
+    pub fn member_reports(&self, ranking: &Ranking) -> Vec<MemberReport> {
+        ranking
+            .rows
+            .iter()
+            .map(|row| {
+                let member = &self.members[&row.id];
+                let days = member
+                    .completions()
+                    .into_iter()
+                    .map(|(day, completion)| {
+                        let since_unlock = |timestamp: Option<u64>| {
+                            let unlock = self.unlock_time(day).unwrap_or(0);
+                            timestamp.map(|timestamp| timestamp.saturating_sub(unlock))
+                        };
+                        DayReport {
+                            day,
+                            part1: since_unlock(completion.part1),
+                            part2: since_unlock(completion.part2),
+                            delta: completion.delta(),
+                        }
+                    })
+                    .collect();
+
+                MemberReport {
+                    id: row.id.clone(),
+                    name: row.name.clone(),
+                    stars: member.stars,
+                    days,
+                }
+            })
+            .collect()
+    }
+}
+
+impl fmt::Display for MemberReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let time = |seconds: Option<u64>| seconds.map_or("-".to_string(), format_seconds);
+
+        writeln!(f, "{} ({} stars)", self.name, self.stars)?;
+        writeln!(
+            f,
+            "{:>5}  {:>12}  {:>12}  {:>12}",
+            "Day", "Part 1", "Part 2", "Delta"
+        )?;
+        for day in &self.days {
+            writeln!(
+                f,
+                "{:>5}  {:>12}  {:>12}  {:>12}",
+                day.day,
+                time(day.part1),
+                time(day.part2),
+                time(day.delta)
+            )?;
+        }
+        Ok(())
+    }
+}
+
+/// Format a number of seconds like `1d 02:03:04`.
+pub fn format_seconds(seconds: u64) -> String {
+    let days = seconds / SECONDS_PER_DAY;
+    let time = format!(
+        "{:02}:{:02}:{:02}",
+        seconds % SECONDS_PER_DAY / 3600,
+        seconds % 3600 / 60,
+        seconds % 60
+    );
+    if days > 0 {
+        format!("{days}d {time}")
+    } else {
+        time
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // unlock of day 1 is at 1669870800
+    const INPUT: &str = r#"{
+        "event": "2022",
+        "owner_id": 1,
+        "members": {
+            "1": {
+                "id": 1, "name": "Alice", "stars": 3, "local_score": 8, "global_score": 0,
+                "last_star_ts": 1669960000,
+                "completion_day_level": {
+                    "1": {
+                        "1": {"get_star_ts": 1669871000, "star_index": 1},
+                        "2": {"get_star_ts": 1669872000, "star_index": 2}
+                    },
+                    "2": {
+                        "1": {"get_star_ts": 1669960000, "star_index": 5}
+                    }
+                }
+            },
+            "2": {
+                "id": 2, "name": null, "stars": 2, "local_score": 5, "global_score": 0,
+                "last_star_ts": 1669871500,
+                "completion_day_level": {
+                    "1": {
+                        "1": {"get_star_ts": 1669871100, "star_index": 3},
+                        "2": {"get_star_ts": 1669871500, "star_index": 4}
+                    }
+                }
+            },
+            "3": {
+                "id": 3, "name": "Carol", "stars": 0, "local_score": 0, "global_score": 0,
+                "last_star_ts": 0,
+                "completion_day_level": {}
+            }
+        }
+    }"#;
+
+    fn ranked(leaderboard: &Leaderboard, scoring: Scoring) -> Vec<(String, u64)> {
+        leaderboard
+            .rank(scoring)
+            .rows
+            .into_iter()
+            .map(|row| (row.name, row.score))
+            .collect()
+    }
+
+    #[test]
+    fn test_member_completions() {
+        let leaderboard = Leaderboard::from_json(INPUT).unwrap();
+        let completions = leaderboard.members["1"].completions();
+        assert_eq!(
+            completions[&1],
+            DayCompletion {
+                part1: Some(1669871000),
+                part2: Some(1669872000)
+            }
+        );
+        assert_eq!(completions[&1].delta(), Some(1000));
+        assert_eq!(completions[&2].delta(), None);
+    }
+
+    #[test]
+    fn test_unlock_time() {
+        let leaderboard = Leaderboard::from_json(INPUT).unwrap();
+        assert_eq!(leaderboard.unlock_time(1), Some(1669870800));
+        assert_eq!(leaderboard.unlock_time(25), Some(1671944400));
+    }
+
+    #[test]
+    fn test_rank_local() {
+        let leaderboard = Leaderboard::from_json(INPUT).unwrap();
+        assert_eq!(
+            ranked(&leaderboard, Scoring::Local),
+            vec![
+                ("Alice".to_string(), 8),
+                ("(anonymous user #2)".to_string(), 5),
+                ("Carol".to_string(), 0)
+            ]
+        );
+        // the computed score matches the one of the website
+        for row in leaderboard.rank(Scoring::Local).rows {
+            assert_eq!(row.score, leaderboard.members[&row.id].local_score);
+        }
+    }
+
+    #[test]
+    fn test_rank_stars_and_delta() {
+        let leaderboard = Leaderboard::from_json(INPUT).unwrap();
+        assert_eq!(
+            ranked(&leaderboard, Scoring::Stars),
+            vec![
+                ("Alice".to_string(), 3),
+                ("(anonymous user #2)".to_string(), 2),
+                ("Carol".to_string(), 0)
+            ]
+        );
+        assert_eq!(
+            ranked(&leaderboard, Scoring::Delta),
+            vec![
+                ("(anonymous user #2)".to_string(), 3),
+                ("Alice".to_string(), 2),
+                ("Carol".to_string(), 0)
+            ]
+        );
+    }
+
+    #[test]
+    fn test_member_reports() {
+        let leaderboard = Leaderboard::from_json(INPUT).unwrap();
+        let reports = leaderboard.member_reports(&leaderboard.rank(Scoring::Local));
+        assert_eq!(reports.len(), 3);
+        assert_eq!(reports[0].name, "Alice");
+        assert_eq!(
+            reports[0].days,
+            vec![
+                DayReport {
+                    day: 1,
+                    part1: Some(200),
+                    part2: Some(1200),
+                    delta: Some(1000)
+                },
+                DayReport {
+                    day: 2,
+                    part1: Some(2800),
+                    part2: None,
+                    delta: None
+                }
+            ]
+        );
+    }
+
+    #[test]
+    fn test_format_seconds() {
+        assert_eq!(format_seconds(59), "00:00:59");
+        assert_eq!(format_seconds(SECONDS_PER_DAY + 3723), "1d 01:02:03");
+    }
+}
@@ -1,10 +1,19 @@
-mod day_01;
-mod day_02;
-mod day_03;
-mod day_04;
+pub mod day_01;
+pub mod day_02;
+pub mod day_03;
+pub mod day_04;
 mod day_05;
 mod day_06;
 
+pub mod alloc;
+pub mod baseline;
+pub mod config;
+pub mod ffi;
+pub mod interval;
+pub mod leaderboard;
+pub mod solver;
+pub mod top_k;
+
 use aoc_runner_derive::aoc_lib;
 
 aoc_lib! { year = 2022 }
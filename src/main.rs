@@ -0,0 +1,692 @@
+use std::{
+    env,
+    error::Error,
+    fs,
+    io::{self, IsTerminal},
+    path::{Path, PathBuf},
+    process::ExitCode,
+    time::Duration,
+};
+
+use aoc_2022::{
+    alloc::AllocStats,
+    baseline::{Baseline, Entry},
+    config::{ColorChoice, Config, OutputFormat, SessionSource},
+    day_01::{self, stats::Statistics},
+    day_02::{
+        self,
+        game::CyclicGame,
+        play::{Opponent, Session},
+        scoring::ScoringRules,
+    },
+    leaderboard::{Leaderboard, Scoring},
+    solver::{self, Measurement, Part, Run},
+};
+use clap::{Args, Parser, Subcommand};
+use serde_json::json;
+
+type Result<T> = std::result::Result<T, Box<dyn Error>>;
+
+#[cfg(feature = "count-alloc")]
+#[global_allocator]
+static GLOBAL: aoc_2022::alloc::CountingAllocator = aoc_2022::alloc::CountingAllocator;
+
+/// Runner for my solutions to Advent of Code 2022.
+#[derive(Parser)]
+#[command(version)]
+struct Cli {
+    #[command(subcommand)]
+    command: Command,
+    /// Configuration file to use instead of the `aoc.toml` found in the working directory or its
+    /// parents.
+    #[arg(long, global = true)]
+    config: Option<PathBuf>,
+    /// Year of the puzzles.
+    #[arg(long, global = true)]
+    year: Option<u16>,
+    /// Format of the output (table or json).
+    #[arg(long, global = true)]
+    format: Option<OutputFormat>,
+    /// Whether to color the output (auto, always or never).
+    #[arg(long, global = true)]
+    color: Option<ColorChoice>,
+    /// How often every part is run when measuring its timings.
+    #[arg(long, global = true)]
+    iterations: Option<usize>,
+}
+
+impl Cli {
+    /// Load the configuration and apply the overrides of the command line.
+    fn config(&self) -> Result<Config> {
+        let mut config = match &self.config {
+            Some(path) => Config::load(path)?,
+            None => Config::discover(&env::current_dir()?)?,
+        };
+
+        if let Some(year) = self.year {
+            config.year = year;
+        }
+        if let Some(format) = self.format {
+            config.format = format;
+        }
+        if let Some(color) = self.color {
+            config.color = color;
+        }
+        if let Some(iterations) = self.iterations {
+            config.iterations = iterations;
+        }
+        Ok(config)
+    }
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Run solutions and report the resources every phase takes.
+    Run(Selection),
+    /// Record or check performance baselines.
+    #[command(subcommand)]
+    Baseline(BaselineCommand),
+    /// Rank the members of a private leaderboard from its JSON export.
+    Leaderboard {
+        /// JSON export of the leaderboard.
+        file: PathBuf,
+        /// Rule to rank the members by (local, stars or delta).
+        #[arg(short, long, default_value = "local")]
+        scoring: Scoring,
+        /// Additionally show the times every member needed for each day.
+        #[arg(long)]
+        members: bool,
+    },
+    /// Show statistics about the calories the elves of day 1 carry.
+    Stats {
+        /// Input to analyze instead of the one of day 1.
+        #[arg(long)]
+        input: Option<PathBuf>,
+        /// Use the example input of day 1.
+        #[arg(long, conflicts_with = "input")]
+        example: bool,
+        /// Number of buckets of the histogram.
+        #[arg(long, default_value_t = 10)]
+        buckets: usize,
+        /// Percentiles to report.
+        #[arg(long, value_delimiter = ',', default_values_t = [10.0, 25.0, 75.0, 90.0])]
+        percentiles: Vec<f64>,
+    },
+    /// Score the strategy guide of day 2 under custom rules.
+    Score {
+        /// TOML file with the points of every hand and outcome, the puzzle's rules by default.
+        #[arg(long)]
+        rules: Option<PathBuf>,
+        /// Game the guide is played in (rps or rpsls).
+        #[arg(long, default_value = "rps")]
+        game: CyclicGame,
+        /// Guide to score instead of the input of day 2.
+        #[arg(long)]
+        input: Option<PathBuf>,
+        /// Use the example input of day 2.
+        #[arg(long, conflicts_with = "input")]
+        example: bool,
+    },
+    /// Play rock-paper-scissors against the strategy guide of day 2 or another opponent.
+    Play {
+        /// Opponent to play against (guide, random, frequency, markov or replay).
+        #[arg(long, default_value = "guide")]
+        opponent: Opponent,
+        /// Seed of the random opponent.
+        #[arg(long, default_value_t = 0)]
+        seed: u64,
+        /// TOML file with the points of every hand and outcome, the puzzle's rules by default.
+        #[arg(long)]
+        rules: Option<PathBuf>,
+        /// Game to play (rps or rpsls).
+        #[arg(long, default_value = "rps")]
+        game: CyclicGame,
+        /// Guide to play against instead of the input of day 2.
+        #[arg(long)]
+        input: Option<PathBuf>,
+        /// Use the example input of day 2.
+        #[arg(long, conflicts_with = "input")]
+        example: bool,
+    },
+    /// Show the configuration in effect.
+    Config,
+}
+
+#[derive(Subcommand)]
+enum BaselineCommand {
+    /// Measure the selected parts and save their timings as baseline.
+    Save(BaselineArgs),
+    /// Measure the selected parts and fail if any phase got slower than in the baseline or has none.
+    Check {
+        #[command(flatten)]
+        args: BaselineArgs,
+        /// Slowdown of the median (in percent) which is tolerated before failing.
+        #[arg(long, default_value_t = 10.0)]
+        tolerance: f64,
+    },
+}
+
+#[derive(Args)]
+struct BaselineArgs {
+    #[command(flatten)]
+    selection: Selection,
+    /// File the baseline is stored in.
+    #[arg(long, default_value = "bench/baseline.json")]
+    file: PathBuf,
+}
+
+/// Arguments selecting the parts to run.
+#[derive(Args)]
+struct Selection {
+    /// Day to run, all days if omitted.
+    #[arg(short, long)]
+    day: Option<u8>,
+    /// Part to run, both parts if omitted.
+    #[arg(short, long, value_parser = clap::value_parser!(u8).range(1..=2))]
+    part: Option<u8>,
+    /// Directory containing the puzzle inputs as `day<N>.txt`.
+    #[arg(long)]
+    input_dir: Option<PathBuf>,
+    /// Use the example inputs instead of the puzzle inputs.
+    #[arg(long, conflicts_with = "input_dir")]
+    example: bool,
+}
+
+fn main() -> ExitCode {
+    let cli = Cli::parse();
+
+    let result = cli.config().and_then(|config| match cli.command {
+        Command::Run(selection) => run(&config, selection),
+        Command::Baseline(BaselineCommand::Save(args)) => save_baseline(&config, args),
+        Command::Baseline(BaselineCommand::Check { args, tolerance }) => {
+            check_baseline(&config, args, tolerance)
+        }
+        Command::Leaderboard {
+            file,
+            scoring,
+            members,
+        } => leaderboard(&config, &file, scoring, members),
+        Command::Stats {
+            input,
+            example,
+            buckets,
+            percentiles,
+        } => stats(&config, input, example, buckets, &percentiles),
+        Command::Score {
+            rules,
+            game,
+            input,
+            example,
+        } => score(&config, rules.as_deref(), &game, input, example),
+        Command::Play {
+            opponent,
+            seed,
+            rules,
+            game,
+            input,
+            example,
+        } => play(
+            &config,
+            opponent,
+            seed,
+            rules.as_deref(),
+            &game,
+            input,
+            example,
+        ),
+        Command::Config => show_config(&config),
+    });
+
+    match result {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(error) => {
+            eprintln!("error: {error}");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+/// A part to run, together with its input.
+struct Puzzle {
+    day: u8,
+    part: Part,
+    input: String,
+    /// The known answer of the part, if there is one.
+    expected: Option<String>,
+}
+
+impl Selection {
+    /// Get all selected parts together with the input of their day.
+    fn load(&self, config: &Config) -> Result<Vec<Puzzle>> {
+        if config.year != 2022 {
+            return Err(format!("there are no solutions for {}", config.year).into());
+        }
+
+        let input_dir = match &self.input_dir {
+            Some(dir) => dir.clone(),
+            None if self.example => config.example_dir(),
+            None => config.input_dir(),
+        };
+
+        let days = match self.day {
+            Some(day) => vec![day],
+            None => solver::available_days().to_vec(),
+        };
+        let parts = match self.part.and_then(Part::from_number) {
+            Some(part) => vec![part],
+            None => vec![Part::One, Part::Two],
+        };
+
+        let mut selected = vec![];
+        for day in days {
+            let file = format!("day{day}.txt");
+            let input = read_file(&input_dir.join(&file))?;
+            // answers are only known for the actual puzzle inputs
+            let answers = match self.example {
+                true => String::new(),
+                false => fs::read_to_string(config.answer_dir().join(&file)).unwrap_or_default(),
+            };
+
+            for &part in &parts {
+                selected.push(Puzzle {
+                    day,
+                    part,
+                    input: input.clone(),
+                    expected: answers
+                        .lines()
+                        .nth(part.number() as usize - 1)
+                        .map(|answer| answer.trim().to_string())
+                        .filter(|answer| !answer.is_empty()),
+                });
+            }
+        }
+        Ok(selected)
+    }
+}
+
+/// Colors text, if enabled.
+struct Style {
+    enabled: bool,
+}
+
+impl Style {
+    fn new(choice: ColorChoice) -> Style {
+        let enabled = match choice {
+            ColorChoice::Always => true,
+            ColorChoice::Never => false,
+            ColorChoice::Auto => io::stdout().is_terminal() && env::var_os("NO_COLOR").is_none(),
+        };
+        Style { enabled }
+    }
+
+    fn paint(&self, text: &str, color: u8) -> String {
+        if self.enabled {
+            format!("\x1b[{color}m{text}\x1b[0m")
+        } else {
+            text.to_string()
+        }
+    }
+
+    fn green(&self, text: &str) -> String {
+        self.paint(text, 32)
+    }
+
+    fn red(&self, text: &str) -> String {
+        self.paint(text, 31)
+    }
+}
+
+/// Get the given input file, or else the (example) input of the given day.
+fn day_input(config: &Config, day: u8, input: Option<PathBuf>, example: bool) -> PathBuf {
+    match input {
+        Some(input) => input,
+        None if example => config.example_dir().join(format!("day{day}.txt")),
+        None => config.input_dir().join(format!("day{day}.txt")),
+    }
+}
+
+fn read_file(path: &Path) -> Result<String> {
+    fs::read_to_string(path)
+        .map_err(|error| format!("could not read {}: {error}", path.display()).into())
+}
+
+/// Run a part as often as configured and report the median time of every phase.
+fn run_repeatedly(config: &Config, puzzle: &Puzzle) -> Result<Run> {
+    let mut runs = vec![];
+    for _ in 0..config.iterations.max(1) {
+        runs.push(solver::run(puzzle.day, puzzle.part, &puzzle.input)?);
+    }
+
+    let median = |phase: fn(&Run) -> Duration| {
+        let mut durations = runs.iter().map(phase).collect::<Vec<_>>();
+        durations.sort();
+        durations[durations.len() / 2]
+    };
+    let generator = median(|run| run.generator.duration);
+    let solver = median(|run| run.solver.duration);
+
+    let mut run = runs.swap_remove(0);
+    run.generator.duration = generator;
+    run.solver.duration = solver;
+    Ok(run)
+}
+
+/// Check an answer against a known one, which may only be the leading value of the answer, e.g.
+/// `24000` for `24000 (elf 4)`.
+fn matches_answer(expected: &str, answer: &str) -> bool {
+    answer == expected
+        || answer
+            .split_once(" (")
+            .is_some_and(|(leading, _)| leading == expected)
+}
+
+fn run(config: &Config, selection: Selection) -> Result<()> {
+    let style = Style::new(config.color);
+    let mut results = vec![];
+    let mut wrong = 0;
+
+    for puzzle in selection.load(config)? {
+        let run = run_repeatedly(config, &puzzle)?;
+        let correct = puzzle
+            .expected
+            .as_ref()
+            .map(|expected| matches_answer(expected, &run.answer));
+        if correct == Some(false) {
+            wrong += 1;
+        }
+
+        match config.format {
+            OutputFormat::Table => {
+                let verdict = match (&puzzle.expected, correct) {
+                    (_, Some(true)) => style.green(" (correct)"),
+                    (Some(expected), _) => style.red(&format!(" (expected {expected})")),
+                    (None, _) => String::new(),
+                };
+                println!(
+                    "Day {} - Part {}: {}{verdict}",
+                    puzzle.day,
+                    puzzle.part.number(),
+                    run.answer
+                );
+                println!("    generator: {}", format_measurement(&run.generator));
+                println!("    solver:    {}", format_measurement(&run.solver));
+            }
+            OutputFormat::Json => results.push(json!({
+                "day": puzzle.day,
+                "part": puzzle.part.number(),
+                "answer": run.answer,
+                "expected": puzzle.expected,
+                "correct": correct,
+                "generator": measurement_json(&run.generator),
+                "solver": measurement_json(&run.solver),
+            })),
+        }
+    }
+
+    if config.format == OutputFormat::Json {
+        println!("{}", serde_json::to_string_pretty(&results)?);
+    }
+    if wrong > 0 {
+        return Err(format!("{wrong} answers differ from the known answers").into());
+    }
+
+    Ok(())
+}
+
+fn measure(config: &Config, args: &BaselineArgs) -> Result<Vec<Entry>> {
+    let mut entries = vec![];
+    for puzzle in args.selection.load(config)? {
+        entries.push(Entry::measure(
+            puzzle.day,
+            puzzle.part,
+            &puzzle.input,
+            config.iterations,
+        )?);
+    }
+    Ok(entries)
+}
+
+fn save_baseline(config: &Config, args: BaselineArgs) -> Result<()> {
+    let baseline = Baseline {
+        entries: measure(config, &args)?,
+    };
+
+    if let Some(parent) = args.file.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    fs::write(&args.file, baseline.to_json())?;
+    println!(
+        "Saved timings of {} parts to {}",
+        baseline.entries.len(),
+        args.file.display()
+    );
+
+    Ok(())
+}
+
+fn check_baseline(config: &Config, args: BaselineArgs, tolerance: f64) -> Result<()> {
+    let style = Style::new(config.color);
+    let baseline = Baseline::from_json(&read_file(&args.file)?)?;
+    let current = measure(config, &args)?;
+    let comparisons = baseline.compare(&current, tolerance);
+    let missing = baseline.missing(&current);
+
+    match config.format {
+        OutputFormat::Table => {
+            for comparison in &comparisons {
+                let line = comparison.to_string();
+                match comparison.regressed {
+                    true => println!("{}", style.red(&line)),
+                    false => println!("{line}"),
+                }
+            }
+            for part in &missing {
+                println!("{}", style.red(&part.to_string()));
+            }
+        }
+        OutputFormat::Json => println!(
+            "{}",
+            serde_json::to_string_pretty(&json!({
+                "comparisons": comparisons,
+                "missing": missing,
+            }))?
+        ),
+    }
+
+    let regressions = comparisons
+        .iter()
+        .filter(|comparison| comparison.regressed)
+        .count();
+    if regressions > 0 {
+        return Err(format!("{regressions} phases regressed by more than {tolerance}%").into());
+    }
+    if !missing.is_empty() {
+        return Err(format!(
+            "{} parts have no baseline, save it again with `baseline save`",
+            missing.len()
+        )
+        .into());
+    }
+
+    Ok(())
+}
+
+fn leaderboard(config: &Config, file: &Path, scoring: Scoring, members: bool) -> Result<()> {
+    let leaderboard = Leaderboard::from_json(&read_file(file)?)?;
+    let ranking = leaderboard.rank(scoring);
+    let reports = leaderboard.member_reports(&ranking);
+
+    match config.format {
+        OutputFormat::Table => {
+            print!("{ranking}");
+            if members {
+                for report in reports {
+                    print!("\n{report}");
+                }
+            }
+        }
+        OutputFormat::Json if members => println!("{}", serde_json::to_string_pretty(&reports)?),
+        OutputFormat::Json => println!("{}", ranking.to_json()),
+    }
+
+    Ok(())
+}
+
+fn stats(
+    config: &Config,
+    input: Option<PathBuf>,
+    example: bool,
+    buckets: usize,
+    percentiles: &[f64],
+) -> Result<()> {
+    let input = day_input(config, 1, input, example);
+    let elves = day_01::try_generator_day01(read_file(&input)?.trim_end_matches('\n'))
+        .map_err(|error| format!("{}: {error}", input.display()))?;
+    let report = Statistics::new(&elves)
+        .ok_or("there are no elves")?
+        .report(percentiles, buckets);
+
+    match config.format {
+        OutputFormat::Table => print!("{report}"),
+        OutputFormat::Json => println!("{}", serde_json::to_string_pretty(&report)?),
+    }
+
+    Ok(())
+}
+
+fn score(
+    config: &Config,
+    rules: Option<&Path>,
+    game: &CyclicGame,
+    input: Option<PathBuf>,
+    example: bool,
+) -> Result<()> {
+    let rules = load_rules(game, rules)?;
+    let guide = read_file(&day_input(config, 2, input, example))?;
+    let part1 = day_02::score_hands(game, &rules, &day_02::parse_hands(game, &guide));
+    let part2 = day_02::score_outcomes(game, &rules, &day_02::parse_outcomes(game, &guide));
+
+    match config.format {
+        OutputFormat::Table => {
+            println!("Part 1: {part1}");
+            println!("Part 2: {part2}");
+        }
+        OutputFormat::Json => println!("{}", json!({ "part1": part1, "part2": part2 })),
+    }
+
+    Ok(())
+}
+
+fn play(
+    config: &Config,
+    opponent: Opponent,
+    seed: u64,
+    rules: Option<&Path>,
+    game: &CyclicGame,
+    input: Option<PathBuf>,
+    example: bool,
+) -> Result<()> {
+    let rules = load_rules(game, rules)?;
+    // only the guide opponent needs a guide, the others are compared against it if there is one
+    let guide = match read_file(&day_input(config, 2, input, example)) {
+        Ok(guide) => day_02::parse_hands(game, &guide),
+        Err(_) if opponent != Opponent::Guide => vec![],
+        Err(error) => return Err(error),
+    };
+
+    let session = Session::new(game, &rules, opponent.strategy(seed, &guide), &guide);
+    session.run(io::stdin().lock(), io::stdout().lock())?;
+    Ok(())
+}
+
+fn load_rules(game: &CyclicGame, path: Option<&Path>) -> Result<ScoringRules> {
+    Ok(match path {
+        Some(path) => ScoringRules::load(game, path)
+            .map_err(|error| format!("{}: {error}", path.display()))?,
+        None => ScoringRules::standard(game),
+    })
+}
+
+fn show_config(config: &Config) -> Result<()> {
+    let file = config
+        .file()
+        .map_or("none (using defaults)".to_string(), |file| {
+            file.display().to_string()
+        });
+    let session = match &config.session {
+        SessionSource::Env(var) => format!("environment variable {var}"),
+        SessionSource::File(_) => {
+            let path = config
+                .session_file()
+                .expect("the session is read from a file");
+            format!("file {}", path.display())
+        }
+        SessionSource::Token(_) => "configuration file".to_string(),
+    };
+    let session_state = match config.session_token() {
+        Ok(Some(_)) => "set",
+        Ok(None) => "not set",
+        Err(_) => "unreadable",
+    };
+
+    println!("config file:  {file}");
+    println!("year:         {}", config.year);
+    println!("inputs:       {}", config.input_dir().display());
+    println!("examples:     {}", config.example_dir().display());
+    println!("answers:      {}", config.answer_dir().display());
+    println!("session:      {session} ({session_state})");
+    println!(
+        "format:       {}",
+        format!("{:?}", config.format).to_lowercase()
+    );
+    println!(
+        "color:        {}",
+        format!("{:?}", config.color).to_lowercase()
+    );
+    println!("iterations:   {}", config.iterations);
+
+    Ok(())
+}
+
+fn measurement_json(measurement: &Measurement) -> serde_json::Value {
+    json!({
+        "duration_ns": measurement.duration.as_nanos() as u64,
+        "allocations": measurement.allocations.map(|stats| json!({
+            "count": stats.allocations,
+            "total_bytes": stats.total_bytes,
+            "peak_bytes": stats.peak_bytes,
+        })),
+    })
+}
+
+fn format_measurement(measurement: &Measurement) -> String {
+    let duration = format!("{:>10.1?}", measurement.duration);
+    match measurement.allocations {
+        Some(AllocStats {
+            allocations,
+            total_bytes,
+            peak_bytes,
+        }) => format!(
+            "{duration} ({allocations} allocations, {} total, {} peak)",
+            format_bytes(total_bytes),
+            format_bytes(peak_bytes)
+        ),
+        None => duration,
+    }
+}
+
+fn format_bytes(bytes: usize) -> String {
+    const UNITS: [&str; 3] = ["KiB", "MiB", "GiB"];
+
+    if bytes < 1024 {
+        return format!("{bytes} B");
+    }
+
+    let mut value = bytes as f64 / 1024.0;
+    let mut unit = 0;
+    while value >= 1024.0 && unit < UNITS.len() - 1 {
+        value /= 1024.0;
+        unit += 1;
+    }
+    format!("{value:.1} {}", UNITS[unit])
+}
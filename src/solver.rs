@@ -0,0 +1,159 @@
+use std::{
+    error::Error,
+    fmt::{self, Display},
+    time::{Duration, Instant},
+};
+
+use crate::{
+    alloc::{self, AllocStats},
+    day_01, day_02, day_03, day_04, day_05, day_06,
+};
+
+/// All days which have a solution in this crate.
+pub const DAYS: [u8; 6] = [1, 2, 3, 4, 5, 6];
+
+/// One of the two parts every puzzle consists of.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Part {
+    One,
+    Two,
+}
+
+impl Part {
+    /// Convert the number of a part (`1` or `2`) into a part.
+    pub fn from_number(part: u8) -> Option<Part> {
+        match part {
+            1 => Some(Part::One),
+            2 => Some(Part::Two),
+            _ => None,
+        }
+    }
+
+    /// Get the number (`1` or `2`) of this part.
+    pub fn number(self) -> u8 {
+        match self {
+            Part::One => 1,
+            Part::Two => 2,
+        }
+    }
+}
+
+/// Error for requests which can not be answered by any solution.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SolveError {
+    UnknownDay(u8),
+    UnknownPart(u8),
+}
+
+impl fmt::Display for SolveError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SolveError::UnknownDay(day) => write!(f, "there is no solution for day {day}"),
+            SolveError::UnknownPart(part) => write!(f, "there is no part {part}"),
+        }
+    }
+}
+
+impl Error for SolveError {}
+
+/// Resources used by a single phase (generating the input or solving) of a solution.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Measurement {
+    pub duration: Duration,
+    /// Only present if allocations are counted, see `alloc::CountingAllocator`.
+    pub allocations: Option<AllocStats>,
+}
+
+impl Measurement {
+    /// Run `f` and measure the resources it takes.
+    fn of<T>(f: impl FnOnce() -> T) -> (T, Measurement) {
+        let start = Instant::now();
+        let (result, allocations) = alloc::track(f);
+        let duration = start.elapsed();
+        (
+            result,
+            Measurement {
+                duration,
+                allocations,
+            },
+        )
+    }
+}
+
+/// Answer of a part together with the resources both of its phases used.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Run {
+    pub answer: String,
+    pub generator: Measurement,
+    pub solver: Measurement,
+}
+
+impl Run {
+    /// Run a generator and the solver working on its output, measuring both separately.
+    pub(crate) fn measure<T, A: Display>(
+        generator: impl FnOnce() -> T,
+        solver: impl FnOnce(&T) -> A,
+    ) -> Run {
+        let (generated, generator) = Measurement::of(generator);
+        let (answer, solver) = Measurement::of(|| solver(&generated));
+        Run {
+            answer: answer.to_string(),
+            generator,
+            solver,
+        }
+    }
+}
+
+/// Get all days which can be solved.
+pub fn available_days() -> &'static [u8] {
+    &DAYS
+}
+
+/// Solve the specified part of a day for the given puzzle input.
+///
+/// Like `cargo aoc`, trailing newlines of the input are ignored. Malformed input panics, just as
+/// it does when running the solutions via `cargo aoc`.
+pub fn solve(day: u8, part: Part, input: &str) -> Result<String, SolveError> {
+    run(day, part, input).map(|run| run.answer)
+}
+
+/// Like `solve`, but additionally measure the generator and the solver of the part.
+pub fn run(day: u8, part: Part, input: &str) -> Result<Run, SolveError> {
+    let input = input.trim_end_matches('\n');
+    let run = match day {
+        1 => day_01::run(part, input),
+        2 => day_02::run(part, input),
+        3 => day_03::run(part, input),
+        4 => day_04::run(part, input),
+        5 => day_05::run(part, input),
+        6 => day_06::run(part, input),
+        _ => return Err(SolveError::UnknownDay(day)),
+    };
+    Ok(run)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_part_from_number() {
+        assert_eq!(Part::from_number(1), Some(Part::One));
+        assert_eq!(Part::from_number(2), Some(Part::Two));
+        assert_eq!(Part::from_number(3), None);
+    }
+
+    #[test]
+    fn test_solve() {
+        assert_eq!(solve(2, Part::One, "A Y\nB X\nC Z\n"), Ok("15".to_string()));
+        assert_eq!(
+            solve(6, Part::Two, "mjqjpqmgbljsphdztnvjfqwrcgsmlb"),
+            Ok("19".to_string())
+        );
+    }
+
+    #[test]
+    fn test_solve_unknown_day() {
+        assert_eq!(solve(25, Part::One, ""), Err(SolveError::UnknownDay(25)));
+    }
+}
@@ -0,0 +1,126 @@
+//! Selection of the k largest values of a sequence without sorting all of it.
+
+use std::{cmp::Reverse, collections::BinaryHeap};
+
+/// A value together with its position in the original sequence.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Ranked<T> {
+    pub index: usize,
+    pub value: T,
+}
+
+/// Keeps the `k` largest values pushed into it.
+///
+/// Pushing is O(log k), so selecting from n values takes O(n log k) time and O(k) memory. Of
+/// equal values, the ones pushed first are kept.
+#[derive(Debug, Clone)]
+pub struct TopK<T> {
+    k: usize,
+    // min-heap, so the smallest kept value is the first one to be evicted. For equal values, the
+    // highest index is the smallest element.
+    heap: BinaryHeap<Reverse<(T, Reverse<usize>)>>,
+}
+
+impl<T: Ord> TopK<T> {
+    /// Create an empty selection of the `k` largest values.
+    pub fn new(k: usize) -> TopK<T> {
+        TopK {
+            k,
+            heap: BinaryHeap::with_capacity(k + 1),
+        }
+    }
+
+    pub fn k(&self) -> usize {
+        self.k
+    }
+
+    /// Number of values currently kept (at most `k`).
+    pub fn len(&self) -> usize {
+        self.heap.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.heap.is_empty()
+    }
+
+    /// Offer a value which was found at the given index.
+    pub fn push(&mut self, index: usize, value: T) {
+        if self.k == 0 {
+            return;
+        }
+
+        let element = Reverse((value, Reverse(index)));
+        if self.heap.len() < self.k {
+            self.heap.push(element);
+        } else if let Some(mut smallest) = self.heap.peek_mut() {
+            if element < *smallest {
+                *smallest = element;
+            }
+        }
+    }
+
+    /// Smallest value which is currently kept.
+    pub fn min(&self) -> Option<&T> {
+        self.heap.peek().map(|Reverse((value, _))| value)
+    }
+
+    /// Get the kept values, largest first.
+    pub fn into_sorted_vec(self) -> Vec<Ranked<T>> {
+        // `into_sorted_vec` sorts ascending, which is descending for the reversed elements
+        self.heap
+            .into_sorted_vec()
+            .into_iter()
+            .map(|Reverse((value, Reverse(index)))| Ranked { index, value })
+            .collect()
+    }
+}
+
+impl<T: Ord + Clone> TopK<T> {
+    /// Get a copy of the kept values, largest first.
+    pub fn to_sorted_vec(&self) -> Vec<Ranked<T>> {
+        self.clone().into_sorted_vec()
+    }
+}
+
+/// Select the `k` largest values (largest first) together with their index in `values`.
+pub fn top_k<T: Ord>(values: impl IntoIterator<Item = T>, k: usize) -> Vec<Ranked<T>> {
+    let mut top = TopK::new(k);
+    for (index, value) in values.into_iter().enumerate() {
+        top.push(index, value);
+    }
+    top.into_sorted_vec()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_top_k() {
+        assert_eq!(
+            top_k([4, 9, 1, 7, 3], 2),
+            vec![Ranked { index: 1, value: 9 }, Ranked { index: 3, value: 7 }]
+        );
+        assert_eq!(top_k([4, 9], 5).len(), 2);
+        assert!(top_k([4, 9], 0).is_empty());
+    }
+
+    #[test]
+    fn test_top_k_keeps_first_of_equal_values() {
+        assert_eq!(
+            top_k([5, 3, 5, 5], 2),
+            vec![Ranked { index: 0, value: 5 }, Ranked { index: 2, value: 5 }]
+        );
+    }
+
+    #[test]
+    fn test_top_k_min() {
+        let mut top = TopK::new(2);
+        assert_eq!(top.min(), None);
+        for (index, value) in [3, 8, 5, 1].into_iter().enumerate() {
+            top.push(index, value);
+        }
+        assert_eq!(top.min(), Some(&5));
+        assert_eq!(top.len(), 2);
+    }
+}
@@ -0,0 +1,31 @@
+//! Counting of allocations with `CountingAllocator` installed, which needs its own test binary.
+#![cfg(feature = "count-alloc")]
+
+use std::hint::black_box;
+
+use aoc_2022::alloc::{self, AllocStats, CountingAllocator};
+
+#[global_allocator]
+static GLOBAL: CountingAllocator = CountingAllocator;
+
+#[test]
+fn test_track_counts_allocations_and_peak() {
+    let (_, stats) = alloc::track(|| {
+        let first = black_box(Vec::<u8>::with_capacity(1000));
+        drop(first);
+        let second = black_box(Vec::<u8>::with_capacity(400));
+        let third = black_box(Vec::<u8>::with_capacity(300));
+        (second, third)
+    });
+
+    // the first block is freed before the others are allocated, so it alone is the peak
+    assert_eq!(
+        stats,
+        Some(AllocStats {
+            allocations: 3,
+            total_bytes: 1700,
+            peak_bytes: 1000,
+        })
+    );
+    assert!(alloc::is_active());
+}